@@ -17,11 +17,26 @@ pub struct GasStationConfig {
     pub bearer: heapless::String<256>,
 }
 
+/// Should follow the network's reference gas price, transactions priced below it are rejected.
+#[derive(serde::Deserialize)]
+pub struct GasConfig {
+    pub price: u64,
+}
+
+impl Default for GasConfig {
+    fn default() -> Self {
+        Self { price: 1000 }
+    }
+}
+
 #[derive(serde::Deserialize)]
 pub struct AppConfig {
     pub contract: ContractConfig,
     pub wifi: WifiConfig,
     pub gas_station: GasStationConfig,
+    /// Optional, configs written before it was added keep the default price.
+    #[serde(default)]
+    pub gas: GasConfig,
 }
 
 pub fn load() -> AppConfig {
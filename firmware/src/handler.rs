@@ -1,20 +1,21 @@
 use crate::tx_builder;
-use crate::tx_builder::{SensorReading, TemperatureReading};
+use crate::tx_builder::{PushReadingArgs, SensorReading, TemperatureReading};
 
 use defmt::info;
 use embedded_nal_async::{Dns, TcpConnect};
-use libs::crypto::Crypto;
-use libs::encoding::{Base64Signature, BcsData};
+use libs::base_types::ObjectID;
+use libs::crypto::Signer;
 use libs::gas_station_client::GasStationClient;
-use libs::transaction_types;
 
-pub async fn run_handler<'a, TCP, DNS>(
-    kp: &mut Crypto,
+pub async fn run_handler<'a, TCP, DNS, S>(
+    signer: &S,
     gas_station_client: &mut GasStationClient<'a, TCP, DNS>,
-    package_id: transaction_types::ObjectID,
+    package_id: ObjectID,
+    gas_price: u64,
 ) where
     TCP: TcpConnect + 'a,
     DNS: Dns + 'a,
+    S: Signer,
 {
     let gas_budget = 100000000;
 
@@ -28,25 +29,33 @@ pub async fn run_handler<'a, TCP, DNS>(
         return;
     }
 
+    let reading = SensorReading {
+        location: heapless::String::try_from("ISS").unwrap(),
+        sensor_id: 123,
+        battery_reading: 255,
+        data: TemperatureReading { temperature: 12345 },
+    };
+    let args = PushReadingArgs::new(&reading).expect("Failed to encode reading");
+    let inputs = args.inputs();
+    let commands = [tx_builder::push_reading_call(package_id)];
+    let gas_payment = [reserved_gas.gas_coins[0]];
+
     let tx = tx_builder::build_temperature_sensor_tx(
-        reserved_gas.sponsor_address.as_tx_object_id(),
-        reserved_gas.gas_coins[0].as_tx_object_ref(),
-        kp.public_address().into(),
-        package_id,
+        reserved_gas.sponsor_address,
+        &gas_payment,
+        signer.address(),
         gas_budget,
-        SensorReading {
-            location: heapless::String::try_from("ISS").unwrap(),
-            sensor_id: 123,
-            battery_reading: 255,
-            data: TemperatureReading { temperature: 12345 },
-        },
-    );
+        gas_price,
+        &inputs,
+        &commands,
+    )
+    .expect("Failed to build tx");
 
-    let tx_bytes = BcsData::new(tx);
-    let signature = Base64Signature::new(&kp.sign(&tx_bytes.as_bcs_bytes().unwrap())).unwrap();
+    let tx_digest = tx.digest().expect("Failed to compute tx digest");
+    info!("Submitting TX: {}", tx_digest);
 
     let executed_tx = gas_station_client
-        .execute_tx(reserved_gas.reservation_id, tx_bytes, signature)
+        .sign_and_execute_tx(reserved_gas.reservation_id, tx, signer)
         .await
         .expect("Failed to execute tx");
 
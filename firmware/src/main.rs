@@ -10,22 +10,16 @@ mod wifi;
 
 use {defmt_rtt as _, panic_probe as _};
 
-use core::ptr::addr_of_mut;
 use embassy_executor::Spawner;
 use embassy_net::dns::DnsSocket;
 use embassy_net::tcp::client::{TcpClient, TcpClientState};
 use embassy_time::Timer;
-use embedded_alloc::LlffHeap as Heap;
 use reqwless::client::HttpClient;
 
 use crate::resources::{AssignedResources, ConfigPins, WiFiPins};
+use libs::base_types::ObjectID;
 use libs::crypto::Crypto;
-use libs::gas_station_client::{GasStationClient, ObjectID};
-
-extern crate alloc;
-
-#[global_allocator]
-static HEAP: Heap = Heap::empty();
+use libs::gas_station_client::GasStationClient;
 
 /*
 pub fn get_sensor_unique_id(pin_flash: Peri<'static, FLASH>) -> u64 {
@@ -40,20 +34,13 @@ pub fn get_sensor_unique_id(pin_flash: Peri<'static, FLASH>) -> u64 {
 
 #[embassy_executor::main]
 async fn main(_spawner: Spawner) {
-    {
-        use core::mem::MaybeUninit;
-        const HEAP_SIZE: usize = 1024 * 10;
-        static mut HEAP_MEM: [MaybeUninit<u8>; HEAP_SIZE] = [MaybeUninit::uninit(); HEAP_SIZE];
-        unsafe { HEAP.init(addr_of_mut!(HEAP_MEM) as usize, HEAP_SIZE) }
-    }
-
     let config = app_config::load();
 
     let p = embassy_rp::init(Default::default());
     let pins: AssignedResources = split_resources!(p);
 
-    // Using a test seed here, in the real world this should be abstracted with an HSM or a secure enclave.
-    let mut kp = Crypto::from_seed([0; 32]);
+    // Using a test seed here. In the real world, plug an HSM or secure element in via `libs::crypto::Signer`.
+    let kp = Crypto::from_seed([0; 32]);
 
     let stack = wifi::initialize_wifi(
         config.wifi.ssid.as_str(),
@@ -73,12 +60,10 @@ async fn main(_spawner: Spawner) {
         config.gas_station.bearer.as_str(),
     );
 
-    let package_id = ObjectID::from_hex(config.contract.package_id.as_str())
-        .unwrap()
-        .as_tx_object_id();
+    let package_id = ObjectID::from_hex(config.contract.package_id.as_str()).unwrap();
 
     loop {
-        handler::run_handler(&mut kp, &mut gas_client, package_id).await;
+        handler::run_handler(&kp, &mut gas_client, package_id, config.gas.price).await;
 
         Timer::after_secs(30).await;
     }
@@ -1,15 +1,29 @@
-use alloc::vec;
-use libs::transaction_types::{
-    Argument, CallArg, Command, GasData, Identifier, ObjectID, ObjectRef, ProgrammableMoveCall,
-    ProgrammableTransaction, TransactionData, TransactionDataV1, TransactionExpiration,
-    TransactionKind,
+use libs::address::IotaAddress;
+use libs::base_types::{Argument, BuilderError, IdentStr, ObjectID, ObjectRef};
+use libs::bcs_stream::{BcsStreamError, to_heapless};
+use libs::transaction_ref::{
+    CallArgRef, CommandRef, ProgrammableMoveCallRef, ProgrammableTransactionRef,
+    TransactionDataRef, TransactionDataRefBuilder,
 };
 use serde::{Deserialize, Serialize};
 
+const TEMPERATURE_MODULE: IdentStr<'static> = IdentStr::from_static("temperature");
+const PUSH_READING_FUNCTION: IdentStr<'static> = IdentStr::from_static("push_reading");
+const PUSH_READING_ARGUMENTS: [Argument; 4] = [
+    Argument::Input(0),
+    Argument::Input(1),
+    Argument::Input(2),
+    Argument::Input(3),
+];
+
+const LOCATION_CAPACITY: usize = 256;
+// A ULEB128 length prefix of up to 2 bytes for strings of at most 256 bytes.
+const LOCATION_BCS_CAPACITY: usize = LOCATION_CAPACITY + 2;
+
 #[derive(Serialize, Debug, Clone, Deserialize)]
 pub struct SensorReading<T: Serialize> {
     pub sensor_id: u8,
-    pub location: heapless::String<256>,
+    pub location: heapless::String<LOCATION_CAPACITY>,
     pub battery_reading: u8,
     pub data: T,
 }
@@ -19,44 +33,60 @@ pub struct TemperatureReading {
     pub temperature: u32,
 }
 
-pub fn build_temperature_sensor_tx(
-    sponsor_address: ObjectID,
-    gas_coin: ObjectRef,
-    sender_address: ObjectID,
-    package_id: ObjectID,
-    gas_budget: u64,
-    reading: SensorReading<TemperatureReading>,
-) -> TransactionData {
-    TransactionData::V1(TransactionDataV1 {
-        expiration: TransactionExpiration::None,
-        sender: sender_address,
-        kind: TransactionKind::ProgrammableTransaction1(ProgrammableTransaction {
-            commands: vec![Command::MoveCall(
-                alloc::boxed::Box::<ProgrammableMoveCall>::new(ProgrammableMoveCall {
-                    package: package_id,
-                    module: Identifier::new(alloc::boxed::Box::from("temperature")),
-                    function: Identifier::new(alloc::boxed::Box::from("push_reading")),
-                    type_arguments: vec![],
-                    arguments: vec![
-                        Argument::Input(0),
-                        Argument::Input(1),
-                        Argument::Input(2),
-                        Argument::Input(3),
-                    ],
-                }),
-            )],
-            inputs: vec![
-                CallArg::Pure(bcs::to_bytes(&reading.sensor_id).unwrap()),
-                CallArg::Pure(bcs::to_bytes(&reading.location).unwrap()),
-                CallArg::Pure(bcs::to_bytes(&reading.battery_reading).unwrap()),
-                CallArg::Pure(bcs::to_bytes(&reading.data.temperature).unwrap()),
-            ],
-        }),
-        gas_data: GasData {
-            budget: gas_budget,
-            owner: sponsor_address,
-            price: 1000,
-            payment: vec![gas_coin],
-        },
+/// The BCS encoded arguments of `temperature::push_reading`, which the transaction borrows.
+pub struct PushReadingArgs {
+    sensor_id: heapless::Vec<u8, 1>,
+    location: heapless::Vec<u8, LOCATION_BCS_CAPACITY>,
+    battery_reading: heapless::Vec<u8, 1>,
+    temperature: heapless::Vec<u8, 4>,
+}
+
+impl PushReadingArgs {
+    pub fn new(reading: &SensorReading<TemperatureReading>) -> Result<Self, BcsStreamError> {
+        Ok(Self {
+            sensor_id: to_heapless(&reading.sensor_id)?,
+            location: to_heapless(&reading.location)?,
+            battery_reading: to_heapless(&reading.battery_reading)?,
+            temperature: to_heapless(&reading.data.temperature)?,
+        })
+    }
+
+    pub fn inputs(&self) -> [CallArgRef<'_>; 4] {
+        [
+            CallArgRef::Pure(&self.sensor_id),
+            CallArgRef::Pure(&self.location),
+            CallArgRef::Pure(&self.battery_reading),
+            CallArgRef::Pure(&self.temperature),
+        ]
+    }
+}
+
+pub fn push_reading_call(package_id: ObjectID) -> CommandRef<'static> {
+    CommandRef::MoveCall(ProgrammableMoveCallRef {
+        package: package_id,
+        module: TEMPERATURE_MODULE,
+        function: PUSH_READING_FUNCTION,
+        type_arguments: &[],
+        arguments: &PUSH_READING_ARGUMENTS,
     })
 }
+
+pub fn build_temperature_sensor_tx<'a>(
+    sponsor_address: IotaAddress,
+    gas_payment: &'a [ObjectRef],
+    sender_address: IotaAddress,
+    gas_budget: u64,
+    gas_price: u64,
+    inputs: &'a [CallArgRef<'a>],
+    commands: &'a [CommandRef<'a>],
+) -> Result<TransactionDataRef<'a>, BuilderError> {
+    TransactionDataRefBuilder::new(
+        ProgrammableTransactionRef { inputs, commands },
+        sender_address,
+    )
+    .sponsor(sponsor_address)
+    .gas_payment(gas_payment)
+    .gas_budget(gas_budget)
+    .gas_price(gas_price)
+    .build()
+}
@@ -0,0 +1,248 @@
+use crate::crypto::SignatureScheme;
+use core::fmt::{Display, Formatter, Write};
+use core::str::FromStr;
+use defmt::Format;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub const IOTA_ADDRESS_LENGTH: usize = 32;
+
+const HEX_LENGTH: usize = IOTA_ADDRESS_LENGTH * 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressError {
+    InvalidHex,
+    TooLong,
+    WrongLength { expected: usize, actual: usize },
+}
+
+impl Display for AddressError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            AddressError::InvalidHex => write!(f, "invalid hex address"),
+            AddressError::TooLong => write!(f, "address has more than {} hex digits", HEX_LENGTH),
+            AddressError::WrongLength { expected, actual } => {
+                write!(f, "expected {} address bytes, got {}", expected, actual)
+            }
+        }
+    }
+}
+
+/// A 32 byte IOTA account address.
+///
+/// Formatted as `0x` followed by 64 lowercase hex digits. Parsing accepts the short forms
+/// with stripped leading zeros (`0x2`) and an optional `0x` prefix.
+/// Serialized as that hex string in human-readable formats (JSON) and as 32 raw bytes in BCS.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Format)]
+pub struct IotaAddress([u8; IOTA_ADDRESS_LENGTH]);
+
+impl IotaAddress {
+    pub const ZERO: Self = Self([0; IOTA_ADDRESS_LENGTH]);
+
+    pub const fn new(bytes: [u8; IOTA_ADDRESS_LENGTH]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AddressError> {
+        let bytes = bytes.try_into().map_err(|_| AddressError::WrongLength {
+            expected: IOTA_ADDRESS_LENGTH,
+            actual: bytes.len(),
+        })?;
+        Ok(Self(bytes))
+    }
+
+    /// `blake2b(flag || public_key)`.
+    /// Ed25519 is the exception, its addresses are derived without the flag byte.
+    pub fn from_public_key(scheme: SignatureScheme, public_key: &[u8]) -> Self {
+        let mut hasher = blake2b_simd::Params::new().hash_length(32).to_state();
+        if scheme != SignatureScheme::Ed25519 {
+            hasher.update(&[scheme.flag()]);
+        }
+        hasher.update(public_key);
+        hasher.finalize().into()
+    }
+
+    pub fn as_bytes(&self) -> &[u8; IOTA_ADDRESS_LENGTH] {
+        &self.0
+    }
+
+    pub fn into_bytes(self) -> [u8; IOTA_ADDRESS_LENGTH] {
+        self.0
+    }
+
+    /// The 64 hex digits without the `0x` prefix.
+    pub fn to_hex(&self) -> heapless::String<HEX_LENGTH> {
+        let mut buf = [0u8; HEX_LENGTH];
+        hex::encode_to_slice(self.0, &mut buf).expect("buffer fits the address");
+        let mut str = heapless::String::new();
+        str.push_str(core::str::from_utf8(&buf).expect("hex is ascii"))
+            .expect("buffer fits the address");
+        str
+    }
+}
+
+impl Display for IotaAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "0x{}", self.to_hex())
+    }
+}
+
+impl core::fmt::Debug for IotaAddress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "IotaAddress({})", self)
+    }
+}
+
+impl FromStr for IotaAddress {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex_str = s.strip_prefix("0x").unwrap_or(s);
+        if hex_str.is_empty() {
+            return Err(AddressError::InvalidHex);
+        }
+
+        let hex_str = hex_str.trim_start_matches('0');
+        if hex_str.len() > HEX_LENGTH {
+            return Err(AddressError::TooLong);
+        }
+
+        let mut padded = [b'0'; HEX_LENGTH];
+        padded[HEX_LENGTH - hex_str.len()..].copy_from_slice(hex_str.as_bytes());
+
+        let mut bytes = [0u8; IOTA_ADDRESS_LENGTH];
+        hex::decode_to_slice(padded, &mut bytes).map_err(|_| AddressError::InvalidHex)?;
+        Ok(Self(bytes))
+    }
+}
+
+impl From<blake2b_simd::Hash> for IotaAddress {
+    fn from(hash: blake2b_simd::Hash) -> Self {
+        Self::from_bytes(hash.as_bytes()).expect("address hashes are 32 bytes")
+    }
+}
+
+impl From<[u8; IOTA_ADDRESS_LENGTH]> for IotaAddress {
+    fn from(bytes: [u8; IOTA_ADDRESS_LENGTH]) -> Self {
+        Self(bytes)
+    }
+}
+
+impl AsRef<[u8]> for IotaAddress {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Serialize for IotaAddress {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let mut str = heapless::String::<{ HEX_LENGTH + 2 }>::new();
+            write!(str, "{}", self).map_err(|_| serde::ser::Error::custom("Invalid hex"))?;
+            serializer.serialize_str(&str)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IotaAddress {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let hex_str = heapless::String::<128>::deserialize(deserializer)?;
+            IotaAddress::from_str(&hex_str).map_err(serde::de::Error::custom)
+        } else {
+            <[u8; IOTA_ADDRESS_LENGTH]>::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "0x689dae2f77b048dcc08e14d73104ea14222b5be14cc31f34a16a1221f944c1e3";
+
+    #[tokio::test]
+    async fn test_parse_and_format() {
+        let address = IotaAddress::from_str(ADDRESS).unwrap();
+        assert_eq!(address.to_string(), ADDRESS);
+        assert_eq!(IotaAddress::from_str(&ADDRESS[2..]).unwrap(), address);
+        assert_eq!(
+            IotaAddress::from_str(&ADDRESS.to_uppercase()[2..]).unwrap(),
+            address
+        );
+
+        let mut framework = [0u8; 32];
+        framework[31] = 2;
+        for short in ["0x2", "2", "0x02", "0x0002"] {
+            assert_eq!(
+                IotaAddress::from_str(short).unwrap(),
+                IotaAddress::new(framework)
+            );
+        }
+        assert_eq!(
+            IotaAddress::new(framework).to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000002"
+        );
+        // More than 64 digits are fine as long as the excess is leading zeros.
+        assert_eq!(
+            IotaAddress::from_str(&alloc::format!("0x00{}", &ADDRESS[2..])).unwrap(),
+            address
+        );
+        assert_eq!(IotaAddress::from_str("0x0").unwrap(), IotaAddress::ZERO);
+
+        assert_eq!(
+            IotaAddress::from_str(&alloc::format!("0x1{}", &ADDRESS[2..])),
+            Err(AddressError::TooLong)
+        );
+        assert_eq!(IotaAddress::from_str("0x"), Err(AddressError::InvalidHex));
+        assert_eq!(IotaAddress::from_str("0xzz"), Err(AddressError::InvalidHex));
+        assert_eq!(
+            IotaAddress::from_bytes(&[0; 20]),
+            Err(AddressError::WrongLength {
+                expected: 32,
+                actual: 20
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_serde() {
+        let address = IotaAddress::from_str(ADDRESS).unwrap();
+
+        let mut buf = [0u8; 128];
+        let size = serde_json_core::to_slice(&address, &mut buf).unwrap();
+        assert_eq!(&buf[..size], alloc::format!("\"{}\"", ADDRESS).as_bytes());
+        let (parsed, _): (IotaAddress, _) = serde_json_core::from_str("\"0x2\"").unwrap();
+        assert_eq!(parsed, IotaAddress::from_str("0x2").unwrap());
+
+        let bcs_bytes = bcs::to_bytes(&address).unwrap();
+        assert_eq!(bcs_bytes.as_slice(), address.as_bytes());
+        assert_eq!(bcs::from_bytes::<IotaAddress>(&bcs_bytes).unwrap(), address);
+    }
+
+    #[tokio::test]
+    async fn test_derivation() {
+        use crate::crypto::{Crypto, KeyPair};
+
+        let kp = Crypto::from_seed([0; 32]);
+        assert_eq!(
+            IotaAddress::from_public_key(SignatureScheme::Ed25519, &kp.public_key_bytes())
+                .to_string(),
+            ADDRESS
+        );
+
+        let kp = KeyPair::from_seed(SignatureScheme::Secp256k1, [1; 32]).unwrap();
+        assert_eq!(
+            IotaAddress::from_public_key(SignatureScheme::Secp256k1, &kp.public_key_bytes())
+                .to_string(),
+            "0xf87edcc926ae7dded7f91ffddcb0ba6c9e3373946e89ec47e478c1bca90c750d"
+        );
+    }
+}
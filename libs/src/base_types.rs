@@ -0,0 +1,414 @@
+//! Identifiers, references and arguments shared by the owned `transaction_types` and the
+//! borrowed `transaction_ref` types, and the checks shared by their builders.
+//! Nothing here allocates.
+
+use crate::address::{AddressError, IOTA_ADDRESS_LENGTH, IotaAddress};
+use crate::bcs_stream::{BcsStreamError, serialize_into};
+use core::fmt::{Debug, Display, Formatter};
+use core::str::FromStr;
+use defmt::Format;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+const TRANSACTION_DATA_DIGEST_PREFIX: &[u8] = b"TransactionData::";
+
+pub type EpochId = u64;
+
+/// Serialized as a `0x` prefixed hex string in human-readable formats (JSON)
+/// and as 32 raw bytes in BCS, the same as `IotaAddress`.
+#[derive(Eq, Default, PartialEq, Ord, PartialOrd, Copy, Clone, Hash, Format)]
+pub struct ObjectID([u8; IOTA_ADDRESS_LENGTH]);
+
+impl ObjectID {
+    pub const fn new(obj_id: [u8; IOTA_ADDRESS_LENGTH]) -> Self {
+        Self(obj_id)
+    }
+
+    /// Accepts the same short forms as `IotaAddress`, e.g. `0x2`.
+    pub fn from_hex(hex_str: &str) -> Result<Self, AddressError> {
+        IotaAddress::from_str(hex_str).map(Self::from)
+    }
+
+    pub fn as_bytes(&self) -> &[u8; IOTA_ADDRESS_LENGTH] {
+        &self.0
+    }
+}
+
+impl FromStr for ObjectID {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_hex(s)
+    }
+}
+
+impl Display for ObjectID {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(&IotaAddress::from(*self), f)
+    }
+}
+
+impl Debug for ObjectID {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "ObjectID({})", self)
+    }
+}
+
+// Object IDs and addresses share the same 32 byte space.
+impl From<IotaAddress> for ObjectID {
+    fn from(address: IotaAddress) -> Self {
+        Self::new(address.into_bytes())
+    }
+}
+
+impl From<ObjectID> for IotaAddress {
+    fn from(id: ObjectID) -> Self {
+        IotaAddress::new(id.0)
+    }
+}
+
+impl Serialize for ObjectID {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        IotaAddress::from(*self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ObjectID {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        IotaAddress::deserialize(deserializer).map(Self::from)
+    }
+}
+
+#[derive(
+    Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash, Default, Debug, Serialize, Deserialize,
+)]
+pub struct SequenceNumber(u64);
+
+impl SequenceNumber {
+    pub fn new(sequence_number: u64) -> Self {
+        Self(sequence_number)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+pub enum DigestError {
+    InvalidBase58,
+    TooLong,
+    WrongLength,
+}
+
+/// Transaction and object digests.
+/// Serialized as a base58 string in human-readable formats (JSON)
+/// and as length prefixed bytes in BCS.
+#[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Format)]
+pub struct Digest([u8; IOTA_ADDRESS_LENGTH]);
+
+impl Digest {
+    pub const ZERO: Self = Digest([0; IOTA_ADDRESS_LENGTH]);
+    /// Marks the reference of a deleted object.
+    pub const OBJECT_DELETED: Self = Digest([99; IOTA_ADDRESS_LENGTH]);
+
+    pub const fn new(digest: [u8; IOTA_ADDRESS_LENGTH]) -> Self {
+        Self(digest)
+    }
+
+    pub fn from_base58(b58_str: &str) -> Result<Self, DigestError> {
+        let mut buffer = [0u8; IOTA_ADDRESS_LENGTH];
+        let length = bs58::decode(b58_str)
+            .onto(&mut buffer)
+            .map_err(|_| DigestError::InvalidBase58)?;
+
+        if length != IOTA_ADDRESS_LENGTH {
+            return Err(DigestError::WrongLength);
+        }
+
+        Ok(Self(buffer))
+    }
+
+    pub fn as_bytes(&self) -> &[u8; IOTA_ADDRESS_LENGTH] {
+        &self.0
+    }
+
+    pub fn as_base58(&self) -> Result<heapless::String<128>, DigestError> {
+        let mut buffer = [0u8; 128];
+        let length = bs58::encode(self.0)
+            .onto(&mut buffer[..])
+            .map_err(|_| DigestError::InvalidBase58)?;
+        let buffer = heapless::Vec::<u8, 128>::from_slice(&buffer[..length])
+            .map_err(|_| DigestError::TooLong)?;
+        let str = heapless::String::from_utf8(buffer).map_err(|_| DigestError::InvalidBase58)?;
+        Ok(str)
+    }
+}
+
+impl Display for Digest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let str = self.as_base58().map_err(|_| core::fmt::Error)?;
+        f.write_str(&str)
+    }
+}
+
+impl Serialize for Digest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            let str = self
+                .as_base58()
+                .map_err(|_| serde::ser::Error::custom("Invalid base58"))?;
+            serializer.serialize_str(&str)
+        } else {
+            serde_bytes::serialize(&self.0, serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Digest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            let b58_str = heapless::String::<128>::deserialize(deserializer)?;
+            Digest::from_base58(&b58_str)
+                .map_err(|_| serde::de::Error::custom("Invalid base58 digest"))
+        } else {
+            serde_bytes::deserialize(deserializer).map(Self)
+        }
+    }
+}
+
+/// A reference to a specific version of an object.
+/// Serializes exactly like the `(ObjectID, SequenceNumber, Digest)` tuple in BCS
+/// and as `{ "objectId", "version", "digest" }` in JSON.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ObjectRef {
+    #[serde(rename = "objectId")]
+    pub object_id: ObjectID,
+    pub version: SequenceNumber,
+    pub digest: Digest,
+}
+
+impl ObjectRef {
+    pub const fn new(object_id: ObjectID, version: SequenceNumber, digest: Digest) -> Self {
+        Self {
+            object_id,
+            version,
+            digest,
+        }
+    }
+}
+
+impl From<(ObjectID, SequenceNumber, Digest)> for ObjectRef {
+    fn from((object_id, version, digest): (ObjectID, SequenceNumber, Digest)) -> Self {
+        Self::new(object_id, version, digest)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentifierError {
+    Empty,
+    /// Byte offset of the first character not allowed at its position.
+    InvalidCharacter(usize),
+}
+
+impl Display for IdentifierError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            IdentifierError::Empty => write!(f, "empty identifier"),
+            IdentifierError::InvalidCharacter(pos) => {
+                write!(f, "invalid identifier character at {}", pos)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum ObjectArg {
+    ImmOrOwnedObject(ObjectRef),
+    SharedObject {
+        id: ObjectID,
+        initial_shared_version: SequenceNumber,
+        mutable: bool,
+    },
+    Receiving(ObjectRef),
+}
+
+impl ObjectArg {
+    pub fn id(&self) -> ObjectID {
+        match self {
+            ObjectArg::ImmOrOwnedObject(object_ref) | ObjectArg::Receiving(object_ref) => {
+                object_ref.object_id
+            }
+            ObjectArg::SharedObject { id, .. } => *id,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Argument {
+    GasCoin,
+    Input(u16),
+    Result(u16),
+    NestedResult(u16, u16),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum TransactionExpiration {
+    None,
+    Epoch(EpochId),
+}
+
+/// Validates a Move identifier: `[a-zA-Z][a-zA-Z0-9_]*` or `_[a-zA-Z0-9_]+`.
+pub(crate) const fn validate_identifier(identifier: &str) -> Result<(), IdentifierError> {
+    let bytes = identifier.as_bytes();
+    match bytes {
+        [] => return Err(IdentifierError::Empty),
+        [b'_'] => return Err(IdentifierError::InvalidCharacter(0)),
+        [first, ..] if !(first.is_ascii_alphabetic() || *first == b'_') => {
+            return Err(IdentifierError::InvalidCharacter(0));
+        }
+        _ => {}
+    }
+
+    let mut i = 1;
+    while i < bytes.len() {
+        if !(bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_') {
+            return Err(IdentifierError::InvalidCharacter(i));
+        }
+        i += 1;
+    }
+    Ok(())
+}
+
+/// A borrowed Move identifier, validated like `Identifier` and serialized the same way.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct IdentStr<'a>(&'a str);
+
+impl<'a> IdentStr<'a> {
+    pub const fn new(identifier: &'a str) -> Result<Self, IdentifierError> {
+        match validate_identifier(identifier) {
+            Ok(()) => Ok(Self(identifier)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Panics if `identifier` is invalid, which fails the build when used in a `const`.
+    pub const fn from_static(identifier: &'static str) -> IdentStr<'static> {
+        if validate_identifier(identifier).is_err() {
+            panic!("invalid Move identifier");
+        }
+        IdentStr(identifier)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn from_validated(identifier: &'a str) -> Self {
+        Self(identifier)
+    }
+
+    pub const fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+impl Display for IdentStr<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// Implemented by the owned and borrowed transaction data, which share one BCS encoding.
+/// Signers and the gas station client accept either.
+pub trait SignableTransaction: Serialize {}
+
+/// `blake2b("TransactionData::" || bcs(tx))`, the digest the node reports for `tx`.
+pub(crate) fn transaction_digest<T: SignableTransaction + ?Sized>(
+    tx: &T,
+) -> Result<Digest, BcsStreamError> {
+    let mut hasher = blake2b_simd::Params::new().hash_length(32).to_state();
+    hasher.update(TRANSACTION_DATA_DIGEST_PREFIX);
+    serialize_into(&mut hasher, tx)?;
+
+    let mut digest = [0u8; IOTA_ADDRESS_LENGTH];
+    digest.copy_from_slice(hasher.finalize().as_bytes());
+    Ok(Digest::new(digest))
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BuilderError {
+    SerializationFailed(BcsStreamError),
+    TooManyInputs,
+    TooManyCommands,
+    /// The same object was passed with a different kind, version or digest.
+    ConflictingObjectInput(ObjectID),
+    MissingGasPrice,
+    MissingGasBudget,
+    NoGasPayment,
+    DuplicateGasPayment(ObjectID),
+    /// The budget has to cover at least one unit of computation at the gas price.
+    GasBudgetTooLow {
+        budget: u64,
+        price: u64,
+    },
+}
+
+impl Display for BuilderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BuilderError::SerializationFailed(err) => write!(f, "pure input: {}", err),
+            BuilderError::TooManyInputs => write!(f, "more than {} inputs", u16::MAX),
+            BuilderError::TooManyCommands => write!(f, "more than {} commands", u16::MAX),
+            BuilderError::ConflictingObjectInput(id) => {
+                write!(f, "conflicting inputs for object {}", id)
+            }
+            BuilderError::MissingGasPrice => write!(f, "gas price not set"),
+            BuilderError::MissingGasBudget => write!(f, "gas budget not set"),
+            BuilderError::NoGasPayment => write!(f, "no gas payment coins"),
+            BuilderError::DuplicateGasPayment(id) => {
+                write!(f, "gas coin {} used more than once", id)
+            }
+            BuilderError::GasBudgetTooLow { budget, price } => {
+                write!(f, "gas budget {} is below the gas price {}", budget, price)
+            }
+        }
+    }
+}
+
+impl From<BcsStreamError> for BuilderError {
+    fn from(err: BcsStreamError) -> Self {
+        BuilderError::SerializationFailed(err)
+    }
+}
+
+/// The gas invariants the node would otherwise reject a transaction for.
+/// Returns the price and budget once both are known to be set.
+pub(crate) fn check_gas(
+    payment: &[ObjectRef],
+    price: Option<u64>,
+    budget: Option<u64>,
+) -> Result<(u64, u64), BuilderError> {
+    let price = price.ok_or(BuilderError::MissingGasPrice)?;
+    let budget = budget.ok_or(BuilderError::MissingGasBudget)?;
+    if budget < price {
+        return Err(BuilderError::GasBudgetTooLow { budget, price });
+    }
+    if payment.is_empty() {
+        return Err(BuilderError::NoGasPayment);
+    }
+    for (i, coin) in payment.iter().enumerate() {
+        if payment[..i].iter().any(|c| c.object_id == coin.object_id) {
+            return Err(BuilderError::DuplicateGasPayment(coin.object_id));
+        }
+    }
+    Ok((price, budget))
+}
@@ -0,0 +1,477 @@
+use core::fmt::{Display, Formatter};
+use serde::{Serialize, ser};
+
+// Same limit as the reference bcs implementation.
+const MAX_SEQUENCE_LENGTH: usize = (1 << 31) - 1;
+
+/// Byte sink for the streaming BCS serializer.
+/// Anything that can absorb bytes incrementally (a hasher, a buffer, ...) can implement this.
+pub trait BcsWrite {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), BcsStreamError>;
+}
+
+impl BcsWrite for blake2b_simd::State {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), BcsStreamError> {
+        self.update(bytes);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BcsWrite for alloc::vec::Vec<u8> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), BcsStreamError> {
+        self.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+impl<const N: usize> BcsWrite for heapless::Vec<u8, N> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), BcsStreamError> {
+        self.extend_from_slice(bytes)
+            .map_err(|_| BcsStreamError::BufferTooSmall)
+    }
+}
+
+/// Writes into a caller provided buffer, for serializing without a heap.
+pub struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// The number of bytes written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl BcsWrite for SliceWriter<'_> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), BcsStreamError> {
+        let end = self.len + bytes.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(BcsStreamError::BufferTooSmall)?
+            .copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BcsStreamError {
+    Custom,
+    NotSupported(&'static str),
+    MissingLength,
+    SequenceTooLong,
+    BufferTooSmall,
+}
+
+impl Display for BcsStreamError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BcsStreamError::Custom => write!(f, "custom serialization error"),
+            BcsStreamError::NotSupported(what) => write!(f, "not supported: {}", what),
+            BcsStreamError::MissingLength => write!(f, "sequence length is unknown"),
+            BcsStreamError::SequenceTooLong => write!(f, "sequence exceeds max length"),
+            BcsStreamError::BufferTooSmall => write!(f, "output buffer too small"),
+        }
+    }
+}
+
+impl ser::StdError for BcsStreamError {}
+
+impl ser::Error for BcsStreamError {
+    fn custom<T: Display>(_msg: T) -> Self {
+        BcsStreamError::Custom
+    }
+}
+
+/// Serializes `value` as BCS directly into `writer`, without an intermediate buffer.
+/// The produced byte stream is identical to `bcs::to_bytes`.
+pub fn serialize_into<W, T>(writer: &mut W, value: &T) -> Result<(), BcsStreamError>
+where
+    W: BcsWrite + ?Sized,
+    T: Serialize + ?Sized,
+{
+    value.serialize(&mut BcsSerializer { writer })
+}
+
+/// Serializes `value` into the start of `buf` and returns the number of bytes written.
+/// Use `serialized_size` to find the buffer size needed.
+pub fn to_slice<T: Serialize + ?Sized>(buf: &mut [u8], value: &T) -> Result<usize, BcsStreamError> {
+    let mut writer = SliceWriter::new(buf);
+    serialize_into(&mut writer, value)?;
+    Ok(writer.len())
+}
+
+pub fn to_heapless<T: Serialize + ?Sized, const N: usize>(
+    value: &T,
+) -> Result<heapless::Vec<u8, N>, BcsStreamError> {
+    let mut bytes = heapless::Vec::new();
+    serialize_into(&mut bytes, value)?;
+    Ok(bytes)
+}
+
+/// The length of the BCS encoding of `value`, computed without allocating.
+pub fn serialized_size<T: Serialize + ?Sized>(value: &T) -> Result<usize, BcsStreamError> {
+    let mut counter = SizeCounter(0);
+    serialize_into(&mut counter, value)?;
+    Ok(counter.0)
+}
+
+struct SizeCounter(usize);
+
+impl BcsWrite for SizeCounter {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), BcsStreamError> {
+        self.0 += bytes.len();
+        Ok(())
+    }
+}
+
+struct BcsSerializer<'w, W: BcsWrite + ?Sized> {
+    writer: &'w mut W,
+}
+
+impl<W: BcsWrite + ?Sized> BcsSerializer<'_, W> {
+    fn write_uleb128(&mut self, mut value: u32) -> Result<(), BcsStreamError> {
+        let mut buf = [0u8; 5];
+        let mut len = 0;
+        while value >= 0x80 {
+            buf[len] = (value as u8 & 0x7f) | 0x80;
+            value >>= 7;
+            len += 1;
+        }
+        buf[len] = value as u8;
+        self.writer.write_bytes(&buf[..len + 1])
+    }
+
+    fn write_length(&mut self, len: usize) -> Result<(), BcsStreamError> {
+        if len > MAX_SEQUENCE_LENGTH {
+            return Err(BcsStreamError::SequenceTooLong);
+        }
+        self.write_uleb128(len as u32)
+    }
+}
+
+impl<W: BcsWrite + ?Sized> ser::Serializer for &mut BcsSerializer<'_, W> {
+    type Ok = ();
+    type Error = BcsStreamError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = ser::Impossible<(), BcsStreamError>;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    fn serialize_bool(self, v: bool) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&[v as u8])
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&[v])
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&v.to_le_bytes())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<(), BcsStreamError> {
+        Err(BcsStreamError::NotSupported("serialize_f32"))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<(), BcsStreamError> {
+        Err(BcsStreamError::NotSupported("serialize_f64"))
+    }
+
+    fn serialize_char(self, _v: char) -> Result<(), BcsStreamError> {
+        Err(BcsStreamError::NotSupported("serialize_char"))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<(), BcsStreamError> {
+        self.serialize_bytes(v.as_bytes())
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<(), BcsStreamError> {
+        self.write_length(v.len())?;
+        self.writer.write_bytes(v)
+    }
+
+    fn serialize_none(self) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&[0])
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), BcsStreamError> {
+        self.writer.write_bytes(&[1])?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), BcsStreamError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), BcsStreamError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), BcsStreamError> {
+        self.write_uleb128(variant_index)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), BcsStreamError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<(), BcsStreamError> {
+        self.write_uleb128(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self, BcsStreamError> {
+        self.write_length(len.ok_or(BcsStreamError::MissingLength)?)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, BcsStreamError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self, BcsStreamError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, BcsStreamError> {
+        self.write_uleb128(variant_index)?;
+        Ok(self)
+    }
+
+    // BCS maps need their entries sorted by serialized key, which requires buffering.
+    // None of the transaction types use maps.
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, BcsStreamError> {
+        Err(BcsStreamError::NotSupported("serialize_map"))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, BcsStreamError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self, BcsStreamError> {
+        self.write_uleb128(variant_index)?;
+        Ok(self)
+    }
+
+    fn collect_str<T: Display + ?Sized>(self, _value: &T) -> Result<(), BcsStreamError> {
+        Err(BcsStreamError::NotSupported("collect_str"))
+    }
+
+    fn is_human_readable(&self) -> bool {
+        false
+    }
+}
+
+impl<W: BcsWrite + ?Sized> ser::SerializeSeq for &mut BcsSerializer<'_, W> {
+    type Ok = ();
+    type Error = BcsStreamError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: BcsWrite + ?Sized> ser::SerializeTuple for &mut BcsSerializer<'_, W> {
+    type Ok = ();
+    type Error = BcsStreamError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: BcsWrite + ?Sized> ser::SerializeTupleStruct for &mut BcsSerializer<'_, W> {
+    type Ok = ();
+    type Error = BcsStreamError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: BcsWrite + ?Sized> ser::SerializeTupleVariant for &mut BcsSerializer<'_, W> {
+    type Ok = ();
+    type Error = BcsStreamError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: BcsWrite + ?Sized> ser::SerializeStruct for &mut BcsSerializer<'_, W> {
+    type Ok = ();
+    type Error = BcsStreamError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+impl<W: BcsWrite + ?Sized> ser::SerializeStructVariant for &mut BcsSerializer<'_, W> {
+    type Ok = ();
+    type Error = BcsStreamError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        _key: &'static str,
+        value: &T,
+    ) -> Result<(), Self::Error> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction_types::TransactionData;
+    use base64::Engine;
+    use base64::prelude::BASE64_STANDARD;
+
+    #[tokio::test]
+    async fn test_stream_matches_bcs() {
+        let move_call = "AAAFAAF7AAsKTHVrYXMgSG9tZQAEAH0AAAAB/wAIAQAAAAAAAAABAMf/nu/aCgOsDfHUaZrN3TD+g8f0TeYhj/yWm9wg0xEOE3RlbXBlcmF0dXJlX3NlbnNvcnMYcHVzaF90ZW1wZXJhdHVyZV9yZWFkaW5nAAUBAAABAQABAgABAwABBACW0s8BcPLRgxRtJMoakExp1rm6dTLkos44avWxmECVlgEAjpZCZJz+EzMlxbhzVc5C7JFdI2OKnXuSaR12/N393gMAAAAAAAAAIK6FX5Rt9gNSKze0pLtiFWdqUtMNtvbWT4qUoKfIR31pltLPAXDy0YMUbSTKGpBMada5unUy5KLOOGr1sZhAlZboAwAAAAAAAADh9QUAAAAAAA==";
+        let mut tx_bytes = [0u8; 1024];
+        let tx_size = BASE64_STANDARD
+            .decode_slice(move_call, &mut tx_bytes)
+            .unwrap();
+        let tx_bytes = &tx_bytes[..tx_size];
+        let tx: TransactionData = bcs::from_bytes(tx_bytes).unwrap();
+
+        let mut writer = alloc::vec::Vec::new();
+        serialize_into(&mut writer, &tx).unwrap();
+        assert_eq!(writer, tx_bytes);
+        assert_eq!(serialized_size(&tx).unwrap(), tx_bytes.len());
+
+        // Into caller buffers, sized exactly by the pre-pass.
+        let mut buf = [0u8; 1024];
+        let size = to_slice(&mut buf[..serialized_size(&tx).unwrap()], &tx).unwrap();
+        assert_eq!(&buf[..size], tx_bytes);
+        assert_eq!(
+            to_slice(&mut buf[..tx_bytes.len() - 1], &tx),
+            Err(BcsStreamError::BufferTooSmall)
+        );
+        assert_eq!(to_heapless::<_, 1024>(&tx).unwrap(), tx_bytes);
+        assert_eq!(
+            to_heapless::<_, 64>(&tx),
+            Err(BcsStreamError::BufferTooSmall)
+        );
+
+        let long = alloc::vec![7u8; 300];
+        let mut writer = alloc::vec::Vec::new();
+        serialize_into(&mut writer, &(Some(-3i64), "ISS", long.as_slice(), true)).unwrap();
+        assert_eq!(
+            writer,
+            bcs::to_bytes(&(Some(-3i64), "ISS", long.as_slice(), true)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,145 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Domain separator prepended to every message before it is hashed and signed.
+/// Serialized as three bytes: `[scope, version, app_id]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Intent {
+    pub scope: IntentScope,
+    pub version: IntentVersion,
+    pub app_id: AppId,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum IntentScope {
+    TransactionData = 0,
+    TransactionEffects = 1,
+    CheckpointSummary = 2,
+    PersonalMessage = 3,
+    SenderSignedTransaction = 4,
+    ProofOfPossession = 5,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum IntentVersion {
+    V0 = 0,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum AppId {
+    Iota = 0,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IntentError {
+    InvalidScope(u8),
+    InvalidVersion(u8),
+    InvalidAppId(u8),
+}
+
+pub const INTENT_SIZE: usize = 3;
+
+impl Intent {
+    pub const fn new(scope: IntentScope, version: IntentVersion, app_id: AppId) -> Self {
+        Self {
+            scope,
+            version,
+            app_id,
+        }
+    }
+
+    pub const fn iota_app(scope: IntentScope) -> Self {
+        Self::new(scope, IntentVersion::V0, AppId::Iota)
+    }
+
+    /// The default intent for user signatures on TransactionData (`[0, 0, 0]`).
+    pub const fn iota_transaction() -> Self {
+        Self::iota_app(IntentScope::TransactionData)
+    }
+
+    pub const fn personal_message() -> Self {
+        Self::iota_app(IntentScope::PersonalMessage)
+    }
+
+    pub const fn proof_of_possession() -> Self {
+        Self::iota_app(IntentScope::ProofOfPossession)
+    }
+
+    pub const fn to_bytes(&self) -> [u8; INTENT_SIZE] {
+        [self.scope as u8, self.version as u8, self.app_id as u8]
+    }
+
+    pub fn from_bytes(bytes: [u8; INTENT_SIZE]) -> Result<Self, IntentError> {
+        Ok(Self {
+            scope: IntentScope::try_from(bytes[0])?,
+            version: IntentVersion::try_from(bytes[1])?,
+            app_id: AppId::try_from(bytes[2])?,
+        })
+    }
+}
+
+impl Default for Intent {
+    fn default() -> Self {
+        Self::iota_transaction()
+    }
+}
+
+/// Three raw bytes in BCS, the prefix of every `IntentMessage`.
+impl Serialize for Intent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_bytes().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Intent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bytes = <[u8; INTENT_SIZE]>::deserialize(deserializer)?;
+        Intent::from_bytes(bytes).map_err(|_| serde::de::Error::custom("Invalid intent"))
+    }
+}
+
+impl TryFrom<u8> for IntentScope {
+    type Error = IntentError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::TransactionData),
+            1 => Ok(Self::TransactionEffects),
+            2 => Ok(Self::CheckpointSummary),
+            3 => Ok(Self::PersonalMessage),
+            4 => Ok(Self::SenderSignedTransaction),
+            5 => Ok(Self::ProofOfPossession),
+            _ => Err(IntentError::InvalidScope(value)),
+        }
+    }
+}
+
+impl TryFrom<u8> for IntentVersion {
+    type Error = IntentError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::V0),
+            _ => Err(IntentError::InvalidVersion(value)),
+        }
+    }
+}
+
+impl TryFrom<u8> for AppId {
+    type Error = IntentError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Iota),
+            _ => Err(IntentError::InvalidAppId(value)),
+        }
+    }
+}
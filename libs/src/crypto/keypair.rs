@@ -0,0 +1,181 @@
+use super::{
+    Crypto, CryptoError, Intent, SECP256K1_PUBLIC_KEY_LENGTH, SECP256K1_SIGNATURE_SIZE,
+    Secp256k1Crypto, Secp256r1Crypto, SignatureScheme,
+};
+use crate::address::IotaAddress;
+use zeroize::Zeroize;
+
+pub const MAX_PUBLIC_KEY_LENGTH: usize = SECP256K1_PUBLIC_KEY_LENGTH;
+pub const MAX_SERIALIZED_SIGNATURE_SIZE: usize = SECP256K1_SIGNATURE_SIZE;
+
+/// Serialized `flag || signature || public_key`, sized for the largest supported scheme.
+pub type SignatureBytes = heapless::Vec<u8, MAX_SERIALIZED_SIGNATURE_SIZE>;
+
+/// Scheme-generic keypair, for code that should not care which key type a device was provisioned with.
+pub enum KeyPair {
+    Ed25519(Crypto),
+    Secp256k1(Secp256k1Crypto),
+    Secp256r1(Secp256r1Crypto),
+}
+
+impl KeyPair {
+    pub fn from_seed(scheme: SignatureScheme, mut seed: [u8; 32]) -> Result<Self, CryptoError> {
+        let keypair = Self::from_seed_ref(scheme, &seed);
+        seed.zeroize();
+        keypair
+    }
+
+    pub fn from_seed_ref(scheme: SignatureScheme, seed: &[u8; 32]) -> Result<Self, CryptoError> {
+        match scheme {
+            SignatureScheme::Ed25519 => Ok(KeyPair::Ed25519(Crypto::from_seed_ref(seed))),
+            SignatureScheme::Secp256k1 => {
+                Ok(KeyPair::Secp256k1(Secp256k1Crypto::from_seed_ref(seed)?))
+            }
+            SignatureScheme::Secp256r1 => {
+                Ok(KeyPair::Secp256r1(Secp256r1Crypto::from_seed_ref(seed)?))
+            }
+        }
+    }
+
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            KeyPair::Ed25519(_) => SignatureScheme::Ed25519,
+            KeyPair::Secp256k1(_) => SignatureScheme::Secp256k1,
+            KeyPair::Secp256r1(_) => SignatureScheme::Secp256r1,
+        }
+    }
+
+    pub fn public_key_bytes(&self) -> heapless::Vec<u8, MAX_PUBLIC_KEY_LENGTH> {
+        let result = match self {
+            KeyPair::Ed25519(kp) => heapless::Vec::from_slice(&kp.public_key_bytes()),
+            KeyPair::Secp256k1(kp) => heapless::Vec::from_slice(&kp.public_key_bytes()),
+            KeyPair::Secp256r1(kp) => heapless::Vec::from_slice(&kp.public_key_bytes()),
+        };
+        result.expect("public key fits MAX_PUBLIC_KEY_LENGTH")
+    }
+
+    pub fn public_address(&self) -> IotaAddress {
+        match self {
+            KeyPair::Ed25519(kp) => kp.public_address(),
+            KeyPair::Secp256k1(kp) => kp.public_address(),
+            KeyPair::Secp256r1(kp) => kp.public_address(),
+        }
+    }
+
+    /// This signs data with the default Intent (3x 0 bytes)
+    pub fn sign(&self, data: &[u8]) -> SignatureBytes {
+        self.sign_with_intent(Intent::iota_transaction(), data)
+    }
+
+    pub fn sign_with_intent(&self, intent: Intent, data: &[u8]) -> SignatureBytes {
+        let result = match self {
+            KeyPair::Ed25519(kp) => SignatureBytes::from_slice(&kp.sign_with_intent(intent, data)),
+            KeyPair::Secp256k1(kp) => {
+                SignatureBytes::from_slice(&kp.sign_with_intent(intent, data))
+            }
+            KeyPair::Secp256r1(kp) => {
+                SignatureBytes::from_slice(&kp.sign_with_intent(intent, data))
+            }
+        };
+        result.expect("signature fits MAX_SERIALIZED_SIGNATURE_SIZE")
+    }
+
+    pub fn sign_bcs<T: serde::Serialize + ?Sized>(
+        &self,
+        intent: Intent,
+        value: &T,
+    ) -> Result<SignatureBytes, CryptoError> {
+        let result = match self {
+            KeyPair::Ed25519(kp) => SignatureBytes::from_slice(&kp.sign_bcs(intent, value)?),
+            KeyPair::Secp256k1(kp) => SignatureBytes::from_slice(&kp.sign_bcs(intent, value)?),
+            KeyPair::Secp256r1(kp) => SignatureBytes::from_slice(&kp.sign_bcs(intent, value)?),
+        };
+        Ok(result.expect("signature fits MAX_SERIALIZED_SIGNATURE_SIZE"))
+    }
+
+    /// Signs `message` the way wallets implement `signPersonalMessage`:
+    /// the message is BCS encoded as `vector<u8>` and signed under `Intent::personal_message()`.
+    pub fn sign_personal_message(&self, message: &[u8]) -> Result<SignatureBytes, CryptoError> {
+        self.sign_bcs(Intent::personal_message(), message)
+    }
+}
+
+impl From<Crypto> for KeyPair {
+    fn from(kp: Crypto) -> Self {
+        KeyPair::Ed25519(kp)
+    }
+}
+
+impl From<Secp256k1Crypto> for KeyPair {
+    fn from(kp: Secp256k1Crypto) -> Self {
+        KeyPair::Secp256k1(kp)
+    }
+}
+
+impl From<Secp256r1Crypto> for KeyPair {
+    fn from(kp: Secp256r1Crypto) -> Self {
+        KeyPair::Secp256r1(kp)
+    }
+}
+
+impl super::Signer for KeyPair {
+    type Error = CryptoError;
+
+    fn scheme(&self) -> SignatureScheme {
+        KeyPair::scheme(self)
+    }
+
+    fn public_key(&self) -> super::PublicKeyBytes {
+        self.public_key_bytes()
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<SignatureBytes, CryptoError> {
+        match self {
+            KeyPair::Ed25519(kp) => kp.sign_digest(digest).await,
+            KeyPair::Secp256k1(kp) => kp.sign_digest(digest).await,
+            KeyPair::Secp256r1(kp) => kp.sign_digest(digest).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_keypair_schemes() {
+        let ed25519 = KeyPair::from_seed(SignatureScheme::Ed25519, [0; 32]).unwrap();
+        let secp256k1 = KeyPair::from_seed(SignatureScheme::Secp256k1, [1; 32]).unwrap();
+
+        assert_eq!(ed25519.scheme(), SignatureScheme::Ed25519);
+        assert_eq!(
+            ed25519.public_address().to_string(),
+            "0x689dae2f77b048dcc08e14d73104ea14222b5be14cc31f34a16a1221f944c1e3"
+        );
+        assert_eq!(ed25519.sign(b"data").len(), 97);
+        assert_eq!(
+            ed25519.sign(b"data").as_slice(),
+            Crypto::from_seed([0; 32]).sign(b"data")
+        );
+
+        assert_eq!(secp256k1.scheme(), SignatureScheme::Secp256k1);
+        assert_eq!(
+            secp256k1.public_address().to_string(),
+            "0xf87edcc926ae7dded7f91ffddcb0ba6c9e3373946e89ec47e478c1bca90c750d"
+        );
+        let sig = secp256k1.sign(b"data");
+        assert_eq!(sig.len(), 98);
+        assert_eq!(sig[0], 0x01);
+        assert_eq!(sig[65..], secp256k1.public_key_bytes());
+
+        let secp256r1 = KeyPair::from_seed(SignatureScheme::Secp256r1, [1; 32]).unwrap();
+        assert_eq!(secp256r1.scheme(), SignatureScheme::Secp256r1);
+        assert_eq!(
+            secp256r1.public_address().to_string(),
+            "0x575dc0072a3309367790cb4415ddc87df5ffa4360ccd2c29f7ec0515026cc0e1"
+        );
+        let sig = secp256r1.sign(b"data");
+        assert_eq!(sig.len(), 98);
+        assert_eq!(sig[0], 0x02);
+    }
+}
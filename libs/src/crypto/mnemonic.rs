@@ -0,0 +1,218 @@
+use super::{Crypto, CryptoError};
+use core::str::FromStr;
+use hmac::{Hmac, Mac};
+use zeroize::{Zeroize, Zeroizing};
+
+pub const IOTA_COIN_TYPE: u32 = 4218;
+
+const HARDENED_OFFSET: u32 = 1 << 31;
+const MAX_PATH_DEPTH: usize = 8;
+
+type HmacSha512 = Hmac<sha2::Sha512>;
+
+/// A SLIP-0010 derivation path. Ed25519 only supports hardened children,
+/// so every index is stored with the hardened bit set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationPath {
+    indices: heapless::Vec<u32, MAX_PATH_DEPTH>,
+}
+
+impl DerivationPath {
+    /// `m/44'/4218'/account'/0'/index'`, the path IOTA wallets use for Ed25519 keys.
+    pub fn iota_ed25519(account: u32, index: u32) -> Result<Self, CryptoError> {
+        Self::from_indices(&[44, IOTA_COIN_TYPE, account, 0, index])
+    }
+
+    /// Takes unhardened index values and hardens them.
+    pub fn from_indices(indices: &[u32]) -> Result<Self, CryptoError> {
+        let mut path = heapless::Vec::new();
+        for index in indices {
+            if *index >= HARDENED_OFFSET {
+                return Err(CryptoError::InvalidDerivationPath);
+            }
+            path.push(index | HARDENED_OFFSET)
+                .map_err(|_| CryptoError::InvalidDerivationPath)?;
+        }
+        Ok(Self { indices: path })
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = CryptoError;
+
+    /// Parses `m/44'/4218'/0'/0'/0'`. Both `'` and `h` mark hardened segments;
+    /// unhardened segments are rejected.
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut segments = path.split('/');
+        if segments.next() != Some("m") {
+            return Err(CryptoError::InvalidDerivationPath);
+        }
+
+        let mut indices = heapless::Vec::<u32, MAX_PATH_DEPTH>::new();
+        for segment in segments {
+            let index = segment
+                .strip_suffix('\'')
+                .or_else(|| segment.strip_suffix('h'))
+                .ok_or(CryptoError::InvalidDerivationPath)?;
+            let index = index
+                .parse::<u32>()
+                .map_err(|_| CryptoError::InvalidDerivationPath)?;
+            indices
+                .push(index)
+                .map_err(|_| CryptoError::InvalidDerivationPath)?;
+        }
+
+        Self::from_indices(&indices)
+    }
+}
+
+/// BIP39 mnemonic to 64-byte seed. The mnemonic must be an English wordlist phrase
+/// with a valid checksum. Only NFKD-normalized input (plain ASCII) is accepted, as
+/// no_std has no Unicode normalization.
+pub fn mnemonic_to_seed(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<Zeroizing<[u8; 64]>, CryptoError> {
+    if !mnemonic.is_ascii() || !passphrase.is_ascii() {
+        return Err(CryptoError::InvalidMnemonic);
+    }
+
+    let mnemonic =
+        bip39::Mnemonic::parse_normalized(mnemonic).map_err(|_| CryptoError::InvalidMnemonic)?;
+
+    // bip39::Mnemonic wipes its word indices on drop.
+    Ok(Zeroizing::new(mnemonic.to_seed_normalized(passphrase)))
+}
+
+/// SLIP-0010 Ed25519 derivation. Returns the private key (usable as `Crypto` seed) of the
+/// node at `path`.
+pub fn derive_ed25519_private_key(seed: &[u8], path: &DerivationPath) -> Zeroizing<[u8; 32]> {
+    let (mut key, mut chain_code) = split_hmac(b"ed25519 seed", &[seed]);
+
+    for index in path.indices() {
+        (key, chain_code) = split_hmac(
+            chain_code.as_slice(),
+            &[&[0], key.as_slice(), &index.to_be_bytes()],
+        );
+    }
+
+    key
+}
+
+fn split_hmac(key: &[u8], data: &[&[u8]]) -> (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>) {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts any key length");
+    for chunk in data {
+        mac.update(chunk);
+    }
+    let mut output = mac.finalize().into_bytes();
+
+    let mut left = Zeroizing::new([0u8; 32]);
+    let mut right = Zeroizing::new([0u8; 32]);
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    output[..].zeroize();
+    (left, right)
+}
+
+impl Crypto {
+    /// Derives the Ed25519 key at `m/44'/4218'/account'/0'/index'`, matching IOTA wallets.
+    pub fn from_mnemonic(
+        mnemonic: &str,
+        passphrase: &str,
+        account: u32,
+        index: u32,
+    ) -> Result<Self, CryptoError> {
+        Self::from_mnemonic_with_path(
+            mnemonic,
+            passphrase,
+            &DerivationPath::iota_ed25519(account, index)?,
+        )
+    }
+
+    pub fn from_mnemonic_with_path(
+        mnemonic: &str,
+        passphrase: &str,
+        path: &DerivationPath,
+    ) -> Result<Self, CryptoError> {
+        let seed = mnemonic_to_seed(mnemonic, passphrase)?;
+        Ok(Self::from_seed_ref(&derive_ed25519_private_key(
+            seed.as_slice(),
+            path,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[tokio::test]
+    async fn test_bip39_seed() {
+        let seed = mnemonic_to_seed(MNEMONIC, "TREZOR").unwrap();
+        assert_eq!(
+            hex::encode(seed.as_slice()),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        // Bad checksum
+        assert_eq!(
+            mnemonic_to_seed(
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon",
+                ""
+            ),
+            Err(CryptoError::InvalidMnemonic)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_slip10_vectors() {
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+
+        let key = derive_ed25519_private_key(&seed, &"m".parse().unwrap());
+        assert_eq!(
+            hex::encode(key.as_slice()),
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+        );
+
+        let key = derive_ed25519_private_key(&seed, &"m/0'".parse().unwrap());
+        assert_eq!(
+            hex::encode(key.as_slice()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+
+        let key = derive_ed25519_private_key(&seed, &"m/0h/1h/2h/2h/1000000000h".parse().unwrap());
+        assert_eq!(
+            hex::encode(key.as_slice()),
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_iota_derivation_path() {
+        assert_eq!(
+            DerivationPath::iota_ed25519(0, 1).unwrap(),
+            "m/44'/4218'/0'/0'/1'".parse().unwrap()
+        );
+        assert!("m/44'/4218'/0'/0/0'".parse::<DerivationPath>().is_err());
+        assert!("44'/4218'".parse::<DerivationPath>().is_err());
+        assert!(DerivationPath::iota_ed25519(HARDENED_OFFSET, 0).is_err());
+
+        let kp = Crypto::from_mnemonic(MNEMONIC, "", 0, 0).unwrap();
+        assert_eq!(
+            kp.public_address().to_hex().as_str(),
+            "365b74f27ca7c6d7ce019d73042f85cc4627e1aeec2b7822994e16010234e576"
+        );
+
+        let kp = Crypto::from_mnemonic(MNEMONIC, "", 0, 1).unwrap();
+        assert_eq!(
+            kp.public_address().to_hex().as_str(),
+            "d82f7aea5c9bc5629957fd4b50fedb9228a50890a7341a5edd08784a27c09dbc"
+        );
+    }
+}
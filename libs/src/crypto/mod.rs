@@ -0,0 +1,445 @@
+mod intent;
+mod keypair;
+mod mnemonic;
+mod multisig;
+mod private_key;
+mod secp256k1;
+mod secp256r1;
+mod signature;
+mod signer;
+
+pub use intent::{AppId, INTENT_SIZE, Intent, IntentError, IntentScope, IntentVersion};
+pub use keypair::{KeyPair, MAX_PUBLIC_KEY_LENGTH, MAX_SERIALIZED_SIGNATURE_SIZE, SignatureBytes};
+pub use mnemonic::{DerivationPath, IOTA_COIN_TYPE, derive_ed25519_private_key, mnemonic_to_seed};
+pub use multisig::{
+    MAX_MULTISIG_MEMBERS, MAX_MULTISIG_SIZE, MULTISIG_FLAG, MultiSig, MultiSigBytes, MultiSigError,
+    MultiSigMember, MultiSigPublicKey,
+};
+pub use private_key::{
+    Bech32PrivateKey, IOTA_PRIV_KEY_PREFIX, decode_private_key, encode_private_key,
+};
+pub use secp256k1::{
+    SECP256K1_PUBLIC_KEY_LENGTH, SECP256K1_SIGNATURE_LENGTH, SECP256K1_SIGNATURE_SIZE,
+    Secp256k1Crypto,
+};
+pub use secp256r1::{
+    SECP256R1_PUBLIC_KEY_LENGTH, SECP256R1_SIGNATURE_LENGTH, SECP256R1_SIGNATURE_SIZE,
+    Secp256r1Crypto,
+};
+pub use signature::SerializedSignature;
+pub use signer::{PublicKeyBytes, Signer};
+
+use crate::address::IotaAddress;
+use crate::bcs_stream::{BcsStreamError, serialize_into};
+use ed25519_dalek::Signer as _;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+pub const ED25519_SIGNATURE_SIZE: usize =
+    ed25519_dalek::SIGNATURE_LENGTH + ed25519_dalek::PUBLIC_KEY_LENGTH + 1;
+
+const KEY_SCHEME_ED25519: u8 = 0;
+const KEY_SCHEME_SECP256K1: u8 = 1;
+const KEY_SCHEME_SECP256R1: u8 = 2;
+
+/// The flag byte in front of serialized signatures and (except for Ed25519) hashed into addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SignatureScheme {
+    Ed25519 = KEY_SCHEME_ED25519,
+    Secp256k1 = KEY_SCHEME_SECP256K1,
+    Secp256r1 = KEY_SCHEME_SECP256R1,
+}
+
+impl SignatureScheme {
+    pub const fn flag(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_flag(flag: u8) -> Result<Self, CryptoError> {
+        match flag {
+            KEY_SCHEME_ED25519 => Ok(SignatureScheme::Ed25519),
+            KEY_SCHEME_SECP256K1 => Ok(SignatureScheme::Secp256k1),
+            KEY_SCHEME_SECP256R1 => Ok(SignatureScheme::Secp256r1),
+            _ => Err(CryptoError::UnknownScheme(flag)),
+        }
+    }
+
+    pub const fn public_key_length(&self) -> usize {
+        match self {
+            SignatureScheme::Ed25519 => ed25519_dalek::PUBLIC_KEY_LENGTH,
+            SignatureScheme::Secp256k1 => SECP256K1_PUBLIC_KEY_LENGTH,
+            SignatureScheme::Secp256r1 => SECP256R1_PUBLIC_KEY_LENGTH,
+        }
+    }
+
+    /// Length of `flag || signature || public_key` for this scheme.
+    pub const fn serialized_signature_size(&self) -> usize {
+        match self {
+            SignatureScheme::Ed25519 => ED25519_SIGNATURE_SIZE,
+            SignatureScheme::Secp256k1 => SECP256K1_SIGNATURE_SIZE,
+            SignatureScheme::Secp256r1 => SECP256R1_SIGNATURE_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CryptoError {
+    SerializationFailed(BcsStreamError),
+    InvalidKey,
+    UnknownScheme(u8),
+    /// A supported scheme where another one was required, e.g. a secp256k1 key passed to
+    /// `Crypto::from_bech32`. Use `KeyPair` to accept any scheme.
+    WrongScheme {
+        expected: SignatureScheme,
+        found: SignatureScheme,
+    },
+    InvalidBase64,
+    WrongLength {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidSignature,
+    InvalidMnemonic,
+    InvalidDerivationPath,
+    InvalidBech32,
+    MultiSig(MultiSigError),
+}
+
+impl From<BcsStreamError> for CryptoError {
+    fn from(err: BcsStreamError) -> Self {
+        CryptoError::SerializationFailed(err)
+    }
+}
+
+impl From<MultiSigError> for CryptoError {
+    fn from(err: MultiSigError) -> Self {
+        CryptoError::MultiSig(err)
+    }
+}
+
+fn intent_hasher(intent: Intent) -> blake2b_simd::State {
+    let mut hasher = blake2b_simd::Params::new().hash_length(32).to_state();
+    hasher.update(&intent.to_bytes());
+    hasher
+}
+
+/// The digest that gets signed.
+fn finalize_digest(hasher: blake2b_simd::State) -> [u8; 32] {
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(hasher.finalize().as_bytes());
+    digest
+}
+
+pub struct Crypto {
+    verifying_key: ed25519_dalek::VerifyingKey,
+    signing_key: ed25519_dalek::SigningKey,
+}
+
+impl Crypto {
+    /// The seed is wiped after use. Callers should zeroize their own copy.
+    pub fn from_seed(mut seed: [u8; 32]) -> Self {
+        let crypto = Self::from_seed_ref(&seed);
+        seed.zeroize();
+        crypto
+    }
+
+    /// Borrows the seed, so a `Zeroizing` seed is never copied out of its wrapper.
+    pub fn from_seed_ref(seed: &[u8; 32]) -> Self {
+        let signing_key = ed25519_dalek::SigningKey::from_bytes(seed);
+        let verifying_key = signing_key.verifying_key();
+
+        Self {
+            verifying_key,
+            signing_key,
+        }
+    }
+
+    /// This signs data with the default Intent (3x 0 bytes)
+    pub fn sign(&self, data: &[u8]) -> [u8; ED25519_SIGNATURE_SIZE] {
+        self.sign_with_intent(Intent::iota_transaction(), data)
+    }
+
+    /// Signs `intent || data`, e.g. `Intent::personal_message()` for non-transaction payloads.
+    pub fn sign_with_intent(&self, intent: Intent, data: &[u8]) -> [u8; ED25519_SIGNATURE_SIZE] {
+        let mut hasher = intent_hasher(intent);
+        hasher.update(data);
+        self.sign_hash(&finalize_digest(hasher))
+    }
+
+    /// Signs the BCS encoding of `value` under `intent`.
+    /// The BCS bytes are streamed into the hasher, so no serialization buffer is needed
+    /// and the payload size is not limited by the stack.
+    pub fn sign_bcs<T: serde::Serialize + ?Sized>(
+        &self,
+        intent: Intent,
+        value: &T,
+    ) -> Result<[u8; ED25519_SIGNATURE_SIZE], CryptoError> {
+        let mut hasher = intent_hasher(intent);
+        serialize_into(&mut hasher, value)?;
+        Ok(self.sign_hash(&finalize_digest(hasher)))
+    }
+
+    /// See [`KeyPair::sign_personal_message`].
+    pub fn sign_personal_message(
+        &self,
+        message: &[u8],
+    ) -> Result<[u8; ED25519_SIGNATURE_SIZE], CryptoError> {
+        self.sign_bcs(Intent::personal_message(), message)
+    }
+
+    fn sign_hash(&self, hash: &[u8; 32]) -> [u8; ED25519_SIGNATURE_SIZE] {
+        let signature = self.signing_key.sign(hash);
+
+        let mut result = [0u8; ED25519_SIGNATURE_SIZE];
+
+        result[0] = SignatureScheme::Ed25519.flag();
+        result[1..1 + ed25519_dalek::SIGNATURE_LENGTH].copy_from_slice(&signature.to_bytes());
+        result[1 + ed25519_dalek::SIGNATURE_LENGTH..]
+            .copy_from_slice(&self.verifying_key.to_bytes());
+
+        result
+    }
+
+    pub fn public_key_bytes(&self) -> [u8; ed25519_dalek::PUBLIC_KEY_LENGTH] {
+        self.verifying_key.to_bytes()
+    }
+
+    pub fn public_address(&self) -> IotaAddress {
+        IotaAddress::from_public_key(SignatureScheme::Ed25519, self.verifying_key.as_bytes())
+    }
+
+    pub fn verifying_key(&self) -> &ed25519_dalek::VerifyingKey {
+        &self.verifying_key
+    }
+
+    #[cfg(feature = "expose_signing_key")]
+    pub fn signing_key(&self) -> &ed25519_dalek::SigningKey {
+        &self.signing_key
+    }
+
+    pub(crate) fn private_key_bytes(&self) -> Zeroizing<[u8; 32]> {
+        Zeroizing::new(self.signing_key.to_bytes())
+    }
+}
+
+// The dalek signing key wipes itself on drop.
+impl ZeroizeOnDrop for Crypto {}
+
+impl Signer for Crypto {
+    type Error = CryptoError;
+
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Ed25519
+    }
+
+    fn public_key(&self) -> PublicKeyBytes {
+        PublicKeyBytes::from_slice(&self.public_key_bytes()).expect("public key fits")
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<SignatureBytes, CryptoError> {
+        Ok(SignatureBytes::from_slice(&self.sign_hash(digest)).expect("signature fits"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_crypto_signing() {
+        let seed = [0; 32];
+        let kp = Crypto::from_seed(seed);
+
+        let expected_tx_bytes = [
+            0, 0, 3, 1, 0, 159, 16, 133, 122, 159, 135, 45, 23, 40, 140, 61, 243, 102, 240, 117,
+            93, 121, 146, 55, 191, 145, 123, 49, 93, 215, 190, 5, 246, 186, 79, 101, 199, 2, 0, 0,
+            0, 0, 0, 0, 0, 32, 186, 128, 247, 107, 53, 48, 75, 82, 7, 165, 148, 125, 118, 84, 34,
+            198, 34, 208, 112, 146, 154, 199, 80, 118, 174, 0, 150, 135, 178, 57, 210, 81, 0, 5, 4,
+            1, 2, 3, 4, 0, 32, 104, 157, 174, 47, 119, 176, 72, 220, 192, 142, 20, 215, 49, 4, 234,
+            20, 34, 43, 91, 225, 76, 195, 31, 52, 161, 106, 18, 33, 249, 68, 193, 227, 3, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            1, 6, 111, 112, 116, 105, 111, 110, 4, 115, 111, 109, 101, 1, 7, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 4, 99, 111,
+            105, 110, 4, 67, 111, 105, 110, 1, 7, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+            0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 4, 105, 111, 116, 97, 4, 73, 79, 84,
+            65, 0, 1, 1, 0, 0, 0, 177, 169, 228, 154, 57, 202, 97, 67, 123, 93, 228, 169, 240, 88,
+            167, 48, 111, 81, 207, 55, 123, 247, 55, 79, 250, 53, 254, 21, 217, 114, 9, 54, 6, 97,
+            110, 99, 104, 111, 114, 15, 115, 116, 97, 114, 116, 95, 110, 101, 119, 95, 99, 104, 97,
+            105, 110, 0, 2, 1, 1, 0, 2, 0, 0, 1, 1, 2, 1, 0, 1, 2, 0, 104, 157, 174, 47, 119, 176,
+            72, 220, 192, 142, 20, 215, 49, 4, 234, 20, 34, 43, 91, 225, 76, 195, 31, 52, 161, 106,
+            18, 33, 249, 68, 193, 227, 1, 79, 244, 153, 214, 156, 202, 32, 208, 123, 245, 9, 36,
+            27, 29, 155, 99, 48, 165, 29, 95, 155, 153, 190, 175, 177, 55, 81, 147, 162, 56, 95,
+            195, 2, 0, 0, 0, 0, 0, 0, 0, 32, 222, 21, 62, 84, 191, 178, 118, 179, 107, 202, 153,
+            233, 66, 155, 176, 234, 127, 34, 26, 71, 52, 97, 163, 23, 136, 83, 149, 57, 44, 143,
+            154, 225, 104, 157, 174, 47, 119, 176, 72, 220, 192, 142, 20, 215, 49, 4, 234, 20, 34,
+            43, 91, 225, 76, 195, 31, 52, 161, 106, 18, 33, 249, 68, 193, 227, 232, 3, 0, 0, 0, 0,
+            0, 0, 128, 150, 152, 0, 0, 0, 0, 0, 0,
+        ];
+
+        let expected_signature = [
+            0, 212, 247, 154, 25, 207, 162, 141, 244, 59, 104, 172, 18, 64, 205, 66, 135, 116, 123,
+            11, 223, 180, 187, 33, 242, 65, 34, 205, 228, 41, 57, 38, 46, 15, 88, 105, 72, 200, 23,
+            76, 202, 121, 195, 242, 206, 234, 74, 40, 212, 235, 27, 231, 72, 148, 215, 174, 65,
+            171, 110, 18, 59, 50, 123, 83, 5, 59, 106, 39, 188, 206, 182, 164, 45, 98, 163, 168,
+            208, 42, 111, 13, 115, 101, 50, 21, 119, 29, 226, 67, 166, 58, 192, 72, 161, 139, 89,
+            218, 41,
+        ];
+
+        /*
+        let expected_hash = [ 102, 190, 162, 202, 227, 156, 171, 230, 94, 111, 99, 3, 219, 125, 36, 76, 227, 226, 62, 100, 148, 24, 216, 212, 101, 28, 33, 207, 106, 113, 229, 35 ];
+        let expected_public_key = [ 59, 106, 39, 188, 206, 182, 164, 45, 98, 163, 168, 208, 42, 111, 13, 115, 101, 50, 21, 119, 29, 226, 67, 166, 58, 192, 72, 161, 139, 89, 218, 41 ];
+        let expected_signed_msg = [ 212, 247, 154, 25, 207, 162, 141, 244, 59, 104, 172, 18, 64, 205, 66, 135, 116, 123, 11, 223, 180, 187, 33, 242, 65, 34, 205, 228, 41, 57, 38, 46, 15, 88, 105, 72, 200, 23, 76, 202, 121, 195, 242, 206, 234, 74, 40, 212, 235, 27, 231, 72, 148, 215, 174, 65, 171, 110, 18, 59, 50, 123, 83, 5 ];
+        let expected_public_key = [ 59, 106, 39, 188, 206, 182, 164, 45, 98, 163, 168, 208, 42, 111, 13, 115, 101, 50, 21, 119, 29, 226, 67, 166, 58, 192, 72, 161, 139, 89, 218, 41 ];
+        */
+
+        let sig = kp.sign(&expected_tx_bytes);
+
+        assert_eq!(sig.as_slice(), expected_signature);
+        assert_eq!(
+            kp.public_address().to_hex().as_str(),
+            "689dae2f77b048dcc08e14d73104ea14222b5be14cc31f34a16a1221f944c1e3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_intent_bytes() {
+        assert_eq!(Intent::iota_transaction().to_bytes(), [0, 0, 0]);
+        assert_eq!(Intent::personal_message().to_bytes(), [3, 0, 0]);
+        assert_eq!(Intent::proof_of_possession().to_bytes(), [5, 0, 0]);
+        assert_eq!(
+            Intent::from_bytes([3, 0, 0]).unwrap(),
+            Intent::personal_message()
+        );
+        assert_eq!(
+            Intent::from_bytes([42, 0, 0]),
+            Err(IntentError::InvalidScope(42))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crypto_signing_with_intent() {
+        let kp = Crypto::from_seed([0; 32]);
+        let data = b"Hello, World";
+
+        let expected_personal_message_signature = [
+            92, 66, 104, 55, 237, 227, 123, 172, 221, 47, 3, 7, 9, 236, 47, 129, 215, 168, 27, 32,
+            77, 187, 9, 30, 38, 166, 143, 43, 80, 94, 195, 112, 120, 180, 20, 249, 117, 247, 127,
+            175, 26, 19, 109, 158, 0, 161, 62, 91, 116, 234, 209, 230, 255, 90, 245, 181, 7, 207,
+            157, 14, 81, 227, 183, 7,
+        ];
+
+        let expected_proof_of_possession_signature = [
+            118, 136, 236, 217, 251, 61, 84, 93, 236, 102, 232, 20, 243, 176, 210, 226, 190, 222,
+            133, 116, 193, 120, 7, 75, 224, 168, 100, 254, 160, 4, 59, 128, 75, 105, 35, 116, 158,
+            33, 86, 197, 20, 85, 77, 20, 239, 224, 245, 166, 21, 235, 233, 8, 69, 34, 212, 224,
+            186, 255, 38, 15, 120, 99, 232, 15,
+        ];
+
+        let sig = kp.sign_with_intent(Intent::personal_message(), data);
+        assert_eq!(sig[0], KEY_SCHEME_ED25519);
+        assert_eq!(sig[1..65], expected_personal_message_signature);
+        assert_eq!(sig[65..], kp.verifying_key().to_bytes());
+
+        let sig = kp.sign_with_intent(Intent::proof_of_possession(), data);
+        assert_eq!(sig[1..65], expected_proof_of_possession_signature);
+
+        assert_eq!(
+            kp.sign_with_intent(Intent::iota_transaction(), data),
+            kp.sign(data)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_crypto_signing_bcs_stream() {
+        let kp = Crypto::from_seed([0; 32]);
+
+        // Larger than the old 5000 byte stack buffer.
+        let payload = alloc::vec![42u8; 8000];
+        let bcs_bytes = bcs::to_bytes(&payload).unwrap();
+
+        assert_eq!(
+            kp.sign_bcs(Intent::iota_transaction(), &payload).unwrap(),
+            kp.sign(&bcs_bytes)
+        );
+        assert_eq!(
+            kp.sign_bcs(Intent::personal_message(), &payload).unwrap(),
+            kp.sign_with_intent(Intent::personal_message(), &bcs_bytes)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_signer_trait() {
+        async fn sign_with<S: Signer>(signer: &S, data: &[u8]) -> SignatureBytes {
+            signer
+                .sign_intent_message(Intent::iota_transaction(), data)
+                .await
+                .unwrap()
+        }
+
+        let data = b"Hello, World";
+
+        let kp = Crypto::from_seed([0; 32]);
+        assert_eq!(Signer::address(&kp), kp.public_address());
+        assert_eq!(sign_with(&kp, data).await.as_slice(), kp.sign(data));
+
+        let kp = Secp256k1Crypto::from_seed([1; 32]).unwrap();
+        assert_eq!(Signer::address(&kp), kp.public_address());
+        assert_eq!(sign_with(&kp, data).await.as_slice(), kp.sign(data));
+
+        let kp = Secp256r1Crypto::from_seed([1; 32]).unwrap();
+        assert_eq!(Signer::address(&kp), kp.public_address());
+        assert_eq!(sign_with(&kp, data).await.as_slice(), kp.sign(data));
+
+        let kp = KeyPair::from_seed(SignatureScheme::Secp256k1, [1; 32]).unwrap();
+        assert_eq!(Signer::address(&kp), kp.public_address());
+        assert_eq!(sign_with(&kp, data).await, kp.sign(data));
+
+        let payload = alloc::vec![42u8; 8000];
+        assert_eq!(
+            Signer::sign_bcs(&kp, Intent::personal_message(), &payload)
+                .await
+                .unwrap(),
+            kp.sign_bcs(Intent::personal_message(), &payload).unwrap()
+        );
+    }
+
+    /// Runs the destructor of `secret` in place and returns the bytes it leaves behind.
+    fn bytes_after_drop<const N: usize>(secret: Zeroizing<[u8; N]>) -> [u8; N] {
+        let mut secret = core::mem::ManuallyDrop::new(secret);
+        let bytes: *const [u8; N] = &**secret;
+        // SAFETY: the storage outlives the drop and any bit pattern is a valid `[u8; N]`.
+        unsafe {
+            core::mem::ManuallyDrop::drop(&mut secret);
+            core::ptr::read_volatile(bytes)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_secrets_wiped_after_use() {
+        let kp = Crypto::from_seed([7; 32]);
+        let private_key = kp.private_key_bytes();
+        assert_eq!(*private_key, [7; 32]);
+        assert_eq!(bytes_after_drop(private_key), [0; 32]);
+
+        let kp = Secp256k1Crypto::from_seed([1; 32]).unwrap();
+        let private_key = kp.private_key_bytes();
+        assert_eq!(*private_key, [1; 32]);
+        assert_eq!(bytes_after_drop(private_key), [0; 32]);
+
+        let kp = Secp256r1Crypto::from_seed([1; 32]).unwrap();
+        let private_key = kp.private_key_bytes();
+        assert_eq!(*private_key, [1; 32]);
+        assert_eq!(bytes_after_drop(private_key), [0; 32]);
+    }
+
+    #[tokio::test]
+    async fn test_sign_hash_matches_sign() {
+        let data = b"sensor reading";
+        let mut hasher = intent_hasher(Intent::iota_transaction());
+        hasher.update(data);
+        let digest = finalize_digest(hasher);
+
+        let kp = Crypto::from_seed([0; 32]);
+        assert_eq!(kp.sign_hash(&digest), kp.sign(data));
+    }
+}
@@ -0,0 +1,607 @@
+use super::{
+    CryptoError, Intent, MAX_PUBLIC_KEY_LENGTH, PublicKeyBytes, SerializedSignature,
+    SignatureBytes, SignatureScheme, intent_hasher,
+};
+use crate::address::IotaAddress;
+use crate::bcs_stream::serialize_into;
+use core::fmt::{Display, Formatter};
+use serde::Serialize;
+
+pub const MULTISIG_FLAG: u8 = 0x03;
+pub const MAX_MULTISIG_MEMBERS: usize = 10;
+
+// All supported schemes use 64 byte signatures.
+const SIGNATURE_LENGTH: usize = 64;
+
+/// Upper bound of `flag || bcs(MultiSig)` for a full committee of the largest keys.
+/// The vector lengths fit into a single ULEB128 byte because of `MAX_MULTISIG_MEMBERS`.
+pub const MAX_MULTISIG_SIZE: usize = 1
+    + (1 + MAX_MULTISIG_MEMBERS * (1 + SIGNATURE_LENGTH))
+    + 2
+    + (1 + MAX_MULTISIG_MEMBERS * (1 + MAX_PUBLIC_KEY_LENGTH + 1))
+    + 2;
+
+pub type MultiSigBytes = heapless::Vec<u8, MAX_MULTISIG_SIZE>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultiSigError {
+    NoMembers,
+    TooManyMembers,
+    ZeroWeight,
+    DuplicateMember,
+    InvalidThreshold,
+    UnknownSigner,
+    DuplicateSignature,
+    InvalidBitmap,
+    Malformed,
+    ThresholdNotReached { weight: u16, threshold: u16 },
+}
+
+impl Display for MultiSigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MultiSigError::NoMembers => write!(f, "multisig has no members"),
+            MultiSigError::TooManyMembers => {
+                write!(f, "multisig has more than {} members", MAX_MULTISIG_MEMBERS)
+            }
+            MultiSigError::ZeroWeight => write!(f, "member weight must not be zero"),
+            MultiSigError::DuplicateMember => write!(f, "duplicate multisig member"),
+            MultiSigError::InvalidThreshold => {
+                write!(f, "threshold must be between 1 and the total weight")
+            }
+            MultiSigError::UnknownSigner => write!(f, "signer is not a multisig member"),
+            MultiSigError::DuplicateSignature => write!(f, "member already signed"),
+            MultiSigError::InvalidBitmap => write!(f, "bitmap does not match the signatures"),
+            MultiSigError::Malformed => write!(f, "malformed multisig bytes"),
+            MultiSigError::ThresholdNotReached { weight, threshold } => {
+                write!(f, "weight {} is below threshold {}", weight, threshold)
+            }
+        }
+    }
+}
+
+/// A public key of a multisig committee and the weight its signature counts with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiSigMember {
+    scheme: SignatureScheme,
+    public_key: PublicKeyBytes,
+    weight: u8,
+}
+
+impl MultiSigMember {
+    pub fn new(
+        scheme: SignatureScheme,
+        public_key: &[u8],
+        weight: u8,
+    ) -> Result<Self, CryptoError> {
+        if public_key.len() != scheme.public_key_length() {
+            return Err(CryptoError::WrongLength {
+                expected: scheme.public_key_length(),
+                actual: public_key.len(),
+            });
+        }
+        if weight == 0 {
+            return Err(MultiSigError::ZeroWeight.into());
+        }
+
+        Ok(Self {
+            scheme,
+            public_key: PublicKeyBytes::from_slice(public_key)
+                .map_err(|_| CryptoError::InvalidKey)?,
+            weight,
+        })
+    }
+
+    pub fn scheme(&self) -> SignatureScheme {
+        self.scheme
+    }
+
+    pub fn public_key(&self) -> &[u8] {
+        &self.public_key
+    }
+
+    pub fn weight(&self) -> u8 {
+        self.weight
+    }
+}
+
+/// The committee behind a multisig address: up to 10 weighted keys and the weight needed to sign.
+/// The member order is part of the address, so all parties must use the same order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiSigPublicKey {
+    members: heapless::Vec<MultiSigMember, MAX_MULTISIG_MEMBERS>,
+    threshold: u16,
+}
+
+impl MultiSigPublicKey {
+    pub fn new(members: &[MultiSigMember], threshold: u16) -> Result<Self, CryptoError> {
+        if members.is_empty() {
+            return Err(MultiSigError::NoMembers.into());
+        }
+        let members =
+            heapless::Vec::from_slice(members).map_err(|_| MultiSigError::TooManyMembers)?;
+
+        for (i, member) in members.iter().enumerate() {
+            if member.weight == 0 {
+                return Err(MultiSigError::ZeroWeight.into());
+            }
+            if members[i + 1..]
+                .iter()
+                .any(|other| other.scheme == member.scheme && other.public_key == member.public_key)
+            {
+                return Err(MultiSigError::DuplicateMember.into());
+            }
+        }
+
+        let total_weight: u16 = members.iter().map(|member| member.weight as u16).sum();
+        if threshold == 0 || threshold > total_weight {
+            return Err(MultiSigError::InvalidThreshold.into());
+        }
+
+        Ok(Self { members, threshold })
+    }
+
+    pub fn members(&self) -> &[MultiSigMember] {
+        &self.members
+    }
+
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// `blake2b(0x03 || threshold || flag_1 || pk_1 || weight_1 || ... || flag_n || pk_n || weight_n)`.
+    /// Unlike single key addresses, Ed25519 members are hashed with their flag byte.
+    pub fn address(&self) -> IotaAddress {
+        let mut hasher = blake2b_simd::Params::new().hash_length(32).to_state();
+        hasher.update(&[MULTISIG_FLAG]);
+        hasher.update(&self.threshold.to_le_bytes());
+        for member in &self.members {
+            hasher.update(&[member.scheme.flag()]);
+            hasher.update(&member.public_key);
+            hasher.update(&[member.weight]);
+        }
+        hasher.finalize().into()
+    }
+
+    fn member_index(&self, scheme: SignatureScheme, public_key: &[u8]) -> Option<usize> {
+        self.members
+            .iter()
+            .position(|member| member.scheme == scheme && member.public_key == public_key)
+    }
+
+    fn write_bcs(&self, out: &mut MultiSigBytes) {
+        push(out, &[self.members.len() as u8]);
+        for member in &self.members {
+            push(out, &[member.scheme.flag()]);
+            push(out, &member.public_key);
+            push(out, &[member.weight]);
+        }
+        push(out, &self.threshold.to_le_bytes());
+    }
+
+    fn read_bcs(reader: &mut Reader<'_>) -> Result<Self, CryptoError> {
+        let len = reader.length()?;
+        let mut members = heapless::Vec::<MultiSigMember, MAX_MULTISIG_MEMBERS>::new();
+        for _ in 0..len {
+            let scheme = SignatureScheme::from_flag(reader.u8()?)?;
+            let public_key = reader.take(scheme.public_key_length())?;
+            let weight = reader.u8()?;
+            members
+                .push(MultiSigMember::new(scheme, public_key, weight)?)
+                .map_err(|_| MultiSigError::TooManyMembers)?;
+        }
+        let threshold = reader.u16()?;
+
+        Self::new(&members, threshold)
+    }
+}
+
+/// A (possibly partial) multisig signature: the committee, a bitmap of the members that signed and
+/// their signatures in member order.
+///
+/// Members can sign independently. A device adds its own signature and sends `to_bytes()` on,
+/// whoever holds the remaining keys restores it with `from_bytes()` and adds theirs until
+/// `is_complete()`. The result is used as the transaction signature of the multisig address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultiSig {
+    public_key: MultiSigPublicKey,
+    bitmap: u16,
+    signatures: heapless::Vec<[u8; SIGNATURE_LENGTH], MAX_MULTISIG_MEMBERS>,
+}
+
+impl MultiSig {
+    pub fn new(public_key: MultiSigPublicKey) -> Self {
+        Self {
+            public_key,
+            bitmap: 0,
+            signatures: heapless::Vec::new(),
+        }
+    }
+
+    /// Aggregates the serialized single key signatures of some of the members.
+    pub fn combine(
+        public_key: MultiSigPublicKey,
+        signatures: &[SerializedSignature],
+    ) -> Result<Self, CryptoError> {
+        let mut multisig = Self::new(public_key);
+        for signature in signatures {
+            multisig.add_signature(signature)?;
+        }
+        Ok(multisig)
+    }
+
+    /// Adds a member's `flag || signature || public_key` signature.
+    /// The signature itself is only checked by `verify`, as the signed message is not known here.
+    pub fn add_signature(&mut self, signature: &SerializedSignature) -> Result<(), CryptoError> {
+        let index = self
+            .public_key
+            .member_index(signature.scheme(), signature.public_key_bytes())
+            .ok_or(MultiSigError::UnknownSigner)?;
+        let bit = 1u16 << index;
+        if self.bitmap & bit != 0 {
+            return Err(MultiSigError::DuplicateSignature.into());
+        }
+
+        let position = (self.bitmap & (bit - 1)).count_ones() as usize;
+        let signature_bytes = signature
+            .signature_bytes()
+            .try_into()
+            .map_err(|_| CryptoError::InvalidSignature)?;
+        self.signatures
+            .insert(position, signature_bytes)
+            .map_err(|_| MultiSigError::TooManyMembers)?;
+        self.bitmap |= bit;
+
+        Ok(())
+    }
+
+    pub fn public_key(&self) -> &MultiSigPublicKey {
+        &self.public_key
+    }
+
+    /// Bit `i` is set if member `i` signed.
+    pub fn bitmap(&self) -> u16 {
+        self.bitmap
+    }
+
+    /// Sum of the weights of the members that signed so far.
+    pub fn weight(&self) -> u16 {
+        self.signers().map(|(member, _)| member.weight as u16).sum()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.weight() >= self.public_key.threshold
+    }
+
+    pub fn address(&self) -> IotaAddress {
+        self.public_key.address()
+    }
+
+    /// `0x03 || bcs(MultiSig)`, the format expected wherever a serialized signature is accepted.
+    pub fn to_bytes(&self) -> MultiSigBytes {
+        let mut out = MultiSigBytes::new();
+        push(&mut out, &[MULTISIG_FLAG]);
+        push(&mut out, &[self.signatures.len() as u8]);
+        for (member, signature) in self.signers() {
+            push(&mut out, &[member.scheme.flag()]);
+            push(&mut out, signature);
+        }
+        push(&mut out, &self.bitmap.to_le_bytes());
+        self.public_key.write_bcs(&mut out);
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let mut reader = Reader { bytes };
+        let flag = reader.u8()?;
+        if flag != MULTISIG_FLAG {
+            return Err(CryptoError::UnknownScheme(flag));
+        }
+
+        let len = reader.length()?;
+        let mut schemes = heapless::Vec::<SignatureScheme, MAX_MULTISIG_MEMBERS>::new();
+        let mut signatures = heapless::Vec::<[u8; SIGNATURE_LENGTH], MAX_MULTISIG_MEMBERS>::new();
+        for _ in 0..len {
+            let scheme = SignatureScheme::from_flag(reader.u8()?)?;
+            let signature = reader
+                .take(SIGNATURE_LENGTH)?
+                .try_into()
+                .map_err(|_| MultiSigError::Malformed)?;
+            // Both vectors are bounded by `length()`.
+            let _ = schemes.push(scheme);
+            let _ = signatures.push(signature);
+        }
+        let bitmap = reader.u16()?;
+        let public_key = MultiSigPublicKey::read_bcs(&mut reader)?;
+        if !reader.bytes.is_empty() {
+            return Err(MultiSigError::Malformed.into());
+        }
+
+        if bitmap >> public_key.members.len() != 0
+            || bitmap.count_ones() as usize != signatures.len()
+        {
+            return Err(MultiSigError::InvalidBitmap.into());
+        }
+
+        let multisig = Self {
+            public_key,
+            bitmap,
+            signatures,
+        };
+        if multisig
+            .signers()
+            .zip(schemes.iter())
+            .any(|((member, _), scheme)| member.scheme != *scheme)
+        {
+            return Err(MultiSigError::InvalidBitmap.into());
+        }
+
+        Ok(multisig)
+    }
+
+    /// Verifies every contained signature over `intent || message` and that their weight reaches
+    /// the threshold. Returns the multisig address, which callers must compare with the expected signer.
+    pub fn verify(&self, message: &[u8], intent: Intent) -> Result<IotaAddress, CryptoError> {
+        let mut hasher = intent_hasher(intent);
+        hasher.update(message);
+        self.verify_digest(hasher.finalize().as_bytes())?;
+        Ok(self.address())
+    }
+
+    /// Same as `verify`, but streams the BCS encoding of `value` instead of taking raw bytes.
+    pub fn verify_bcs<T: Serialize + ?Sized>(
+        &self,
+        value: &T,
+        intent: Intent,
+    ) -> Result<IotaAddress, CryptoError> {
+        let mut hasher = intent_hasher(intent);
+        serialize_into(&mut hasher, value)?;
+        self.verify_digest(hasher.finalize().as_bytes())?;
+        Ok(self.address())
+    }
+
+    /// Verifies a multisig over a personal message, see `SerializedSignature::verify_personal_message`.
+    pub fn verify_personal_message(&self, message: &[u8]) -> Result<IotaAddress, CryptoError> {
+        self.verify_bcs(message, Intent::personal_message())
+    }
+
+    fn verify_digest(&self, digest: &[u8]) -> Result<(), CryptoError> {
+        if !self.is_complete() {
+            return Err(MultiSigError::ThresholdNotReached {
+                weight: self.weight(),
+                threshold: self.public_key.threshold,
+            }
+            .into());
+        }
+
+        for (member, signature) in self.signers() {
+            let mut bytes = SignatureBytes::new();
+            let _ = bytes.push(member.scheme.flag());
+            let _ = bytes.extend_from_slice(signature);
+            let _ = bytes.extend_from_slice(&member.public_key);
+
+            SerializedSignature::from_bytes(&bytes)?.verify_digest(digest)?;
+        }
+
+        Ok(())
+    }
+
+    /// The members that signed, paired with their signatures.
+    fn signers(&self) -> impl Iterator<Item = (&MultiSigMember, &[u8; SIGNATURE_LENGTH])> {
+        self.public_key
+            .members
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.bitmap & (1 << i) != 0)
+            .map(|(_, member)| member)
+            .zip(self.signatures.iter())
+    }
+}
+
+impl TryFrom<&[u8]> for MultiSig {
+    type Error = CryptoError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+// `MultiSigBytes` is sized for the largest committee, so pushing can't fail.
+fn push(out: &mut MultiSigBytes, bytes: &[u8]) {
+    out.extend_from_slice(bytes)
+        .expect("multisig fits MAX_MULTISIG_SIZE");
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], CryptoError> {
+        if self.bytes.len() < len {
+            return Err(MultiSigError::Malformed.into());
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, CryptoError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, CryptoError> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    /// A ULEB128 vector length. Anything above `MAX_MULTISIG_MEMBERS` is rejected,
+    /// so only single byte lengths need to be handled.
+    fn length(&mut self) -> Result<usize, CryptoError> {
+        let len = self.u8()? as usize;
+        if len > MAX_MULTISIG_MEMBERS {
+            return Err(MultiSigError::TooManyMembers.into());
+        }
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{Crypto, KeyPair};
+    use base64::prelude::*;
+
+    fn committee() -> (Crypto, KeyPair, KeyPair, MultiSigPublicKey) {
+        let device = Crypto::from_seed([1; 32]);
+        let backup = KeyPair::from_seed(SignatureScheme::Secp256k1, [2; 32]).unwrap();
+        let operator = KeyPair::from_seed(SignatureScheme::Secp256r1, [3; 32]).unwrap();
+
+        let public_key = MultiSigPublicKey::new(
+            &[
+                MultiSigMember::new(SignatureScheme::Ed25519, &device.public_key_bytes(), 1)
+                    .unwrap(),
+                MultiSigMember::new(SignatureScheme::Secp256k1, &backup.public_key_bytes(), 1)
+                    .unwrap(),
+                MultiSigMember::new(SignatureScheme::Secp256r1, &operator.public_key_bytes(), 2)
+                    .unwrap(),
+            ],
+            3,
+        )
+        .unwrap();
+
+        (device, backup, operator, public_key)
+    }
+
+    #[tokio::test]
+    async fn test_multisig_address() {
+        let (_, _, _, public_key) = committee();
+
+        assert_eq!(
+            public_key.address().to_hex().as_str(),
+            "cf8a44526c0130da2e8a146dded8255c2f782f465fae6acdf39455e779fe25b9"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_signature_completed_later() {
+        let data = b"Hello, World";
+        let (device, backup, operator, public_key) = committee();
+
+        // The device signs first and hands the partial signature on.
+        let device_sig = SerializedSignature::from_bytes(&device.sign(data)).unwrap();
+        let mut partial = MultiSig::new(public_key.clone());
+        partial.add_signature(&device_sig).unwrap();
+        assert!(!partial.is_complete());
+        assert_eq!(
+            partial.verify(data, Intent::iota_transaction()),
+            Err(CryptoError::MultiSig(MultiSigError::ThresholdNotReached {
+                weight: 1,
+                threshold: 3
+            }))
+        );
+        let partial_bytes = partial.to_bytes();
+
+        // The backend restores it and adds the operator signature.
+        let operator_sig = SerializedSignature::from_bytes(&operator.sign(data)).unwrap();
+        let mut multisig = MultiSig::from_bytes(&partial_bytes).unwrap();
+        assert_eq!(multisig, partial);
+        multisig.add_signature(&operator_sig).unwrap();
+        assert!(multisig.is_complete());
+        assert_eq!(multisig.bitmap(), 0b101);
+        assert_eq!(multisig.weight(), 3);
+        assert_eq!(
+            multisig.verify(data, Intent::iota_transaction()).unwrap(),
+            public_key.address()
+        );
+        assert_eq!(
+            multisig.verify(data, Intent::personal_message()),
+            Err(CryptoError::InvalidSignature)
+        );
+
+        // Signatures are kept in member order, whichever order they arrive in.
+        let reversed =
+            MultiSig::combine(public_key.clone(), &[operator_sig, device_sig.clone()]).unwrap();
+        assert_eq!(reversed.to_bytes(), multisig.to_bytes());
+        assert_eq!(
+            reversed.to_bytes().len(),
+            1 + 1 + 2 * 65 + 2 + 1 + (1 + 32 + 1) + 2 * (1 + 33 + 1) + 2
+        );
+
+        // Weight 2 of 3 is not enough.
+        let backup_sig = SerializedSignature::from_bytes(&backup.sign(data)).unwrap();
+        let multisig = MultiSig::combine(public_key, &[device_sig, backup_sig]).unwrap();
+        assert!(!multisig.is_complete());
+    }
+
+    #[tokio::test]
+    async fn test_multisig_errors() {
+        let data = b"Hello, World";
+        let (device, _, _, public_key) = committee();
+        let device_sig = SerializedSignature::from_bytes(&device.sign(data)).unwrap();
+
+        let mut multisig = MultiSig::new(public_key.clone());
+        multisig.add_signature(&device_sig).unwrap();
+        assert_eq!(
+            multisig.add_signature(&device_sig),
+            Err(CryptoError::MultiSig(MultiSigError::DuplicateSignature))
+        );
+
+        let stranger = Crypto::from_seed([9; 32]);
+        let stranger_sig = SerializedSignature::from_bytes(&stranger.sign(data)).unwrap();
+        assert_eq!(
+            multisig.add_signature(&stranger_sig),
+            Err(CryptoError::MultiSig(MultiSigError::UnknownSigner))
+        );
+
+        let member = public_key.members()[0].clone();
+        assert_eq!(
+            MultiSigPublicKey::new(core::slice::from_ref(&member), 2),
+            Err(CryptoError::MultiSig(MultiSigError::InvalidThreshold))
+        );
+        assert_eq!(
+            MultiSigPublicKey::new(&[member.clone(), member.clone()], 1),
+            Err(CryptoError::MultiSig(MultiSigError::DuplicateMember))
+        );
+        assert_eq!(
+            MultiSigPublicKey::new(&[], 1),
+            Err(CryptoError::MultiSig(MultiSigError::NoMembers))
+        );
+        assert_eq!(
+            MultiSigMember::new(SignatureScheme::Ed25519, &device.public_key_bytes(), 0),
+            Err(CryptoError::MultiSig(MultiSigError::ZeroWeight))
+        );
+
+        // A bitmap bit pointing past the members.
+        let mut bytes = multisig.to_bytes();
+        bytes[2 + 65] = 0b1000;
+        assert_eq!(
+            MultiSig::from_bytes(&bytes),
+            Err(CryptoError::MultiSig(MultiSigError::InvalidBitmap))
+        );
+        let bytes = multisig.to_bytes();
+        assert_eq!(
+            MultiSig::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(CryptoError::MultiSig(MultiSigError::Malformed))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reference_encoding() {
+        // 2-of-3 (ed25519, secp256k1, secp256r1) committee signed by the first two members.
+        let encoded = "AwIATFuDqu1zIzuXOhy4EwSa1g2bAhHGRdhDzn+vH3X9WmN3t6bwN3JpNLYzJlxQffgOidjaIQqUxW/wrFxAY7QnDAH62mjDDjMxvNedeOY5a2OOEW6dHdSUCTid4Y4BHtsVQRT90EfeCFWZOrLMmMbHnKQQPq3uXqlUWnL2RkqGzT5DAwADAA19qzWMja2qTvoASadbB0NlVbEKNoIZu2gPcFcTSdd1AQECDhfNWTnkaxlmQZaM11mRC6JXfif6c/3jh225vsW86ysBAgNH+68j8DirxMMIonEReipM/67dvF/4HHUXvGx0p+20MAECAA==";
+        let mut buf = [0u8; MAX_MULTISIG_SIZE];
+        let size = BASE64_STANDARD.decode_slice(encoded, &mut buf).unwrap();
+
+        let multisig = MultiSig::from_bytes(&buf[..size]).unwrap();
+        assert_eq!(multisig.bitmap(), 0b011);
+        assert_eq!(multisig.public_key().threshold(), 2);
+        assert_eq!(multisig.public_key().members().len(), 3);
+        assert_eq!(
+            multisig.public_key().members()[1].scheme(),
+            SignatureScheme::Secp256k1
+        );
+        assert!(multisig.is_complete());
+        assert_eq!(multisig.to_bytes().as_slice(), &buf[..size]);
+    }
+}
@@ -0,0 +1,199 @@
+use super::{Crypto, CryptoError, KeyPair, Secp256k1Crypto, Secp256r1Crypto, SignatureScheme};
+use bech32::primitives::decode::CheckedHrpstring;
+use bech32::{Bech32, Hrp};
+use zeroize::Zeroizing;
+
+/// Human readable part of bech32 private keys, as used by `iota keytool export/import`.
+pub const IOTA_PRIV_KEY_PREFIX: &str = "iotaprivkey";
+
+/// `iotaprivkey1...` is 71 characters for 32 byte keys.
+pub type Bech32PrivateKey = heapless::String<128>;
+
+/// Encodes `flag || private_key` as bech32 with the `iotaprivkey` prefix.
+pub fn encode_private_key(
+    scheme: SignatureScheme,
+    private_key: &[u8; 32],
+) -> Result<Bech32PrivateKey, CryptoError> {
+    let mut data = Zeroizing::new([0u8; 33]);
+    data[0] = scheme.flag();
+    data[1..].copy_from_slice(private_key);
+
+    let hrp = Hrp::parse(IOTA_PRIV_KEY_PREFIX).map_err(|_| CryptoError::InvalidBech32)?;
+    let mut result = Bech32PrivateKey::new();
+    bech32::encode_lower_to_fmt::<Bech32, _>(&mut result, hrp, data.as_slice())
+        .map_err(|_| CryptoError::InvalidBech32)?;
+
+    Ok(result)
+}
+
+/// Decodes an `iotaprivkey1...` string into its scheme and 32 byte private key.
+pub fn decode_private_key(
+    bech32_str: &str,
+) -> Result<(SignatureScheme, Zeroizing<[u8; 32]>), CryptoError> {
+    let checked =
+        CheckedHrpstring::new::<Bech32>(bech32_str).map_err(|_| CryptoError::InvalidBech32)?;
+
+    if checked.hrp().as_str() != IOTA_PRIV_KEY_PREFIX {
+        return Err(CryptoError::InvalidBech32);
+    }
+
+    let mut data = Zeroizing::new([0u8; 33]);
+    let mut length = 0;
+    for byte in checked.byte_iter() {
+        if let Some(slot) = data.get_mut(length) {
+            *slot = byte;
+        }
+        length += 1;
+    }
+
+    if length != data.len() {
+        return Err(CryptoError::WrongLength {
+            expected: data.len(),
+            actual: length,
+        });
+    }
+
+    let scheme = SignatureScheme::from_flag(data[0])?;
+    let mut private_key = Zeroizing::new([0u8; 32]);
+    private_key.copy_from_slice(&data[1..]);
+
+    Ok((scheme, private_key))
+}
+
+impl Crypto {
+    /// Imports a key produced by `iota keytool export`. Fails with `WrongScheme` for
+    /// non-Ed25519 keys, which `KeyPair::from_bech32` imports.
+    pub fn from_bech32(bech32_str: &str) -> Result<Self, CryptoError> {
+        match decode_private_key(bech32_str)? {
+            (SignatureScheme::Ed25519, private_key) => Ok(Self::from_seed_ref(&private_key)),
+            (found, _) => Err(CryptoError::WrongScheme {
+                expected: SignatureScheme::Ed25519,
+                found,
+            }),
+        }
+    }
+
+    pub fn to_bech32(&self) -> Result<Bech32PrivateKey, CryptoError> {
+        encode_private_key(SignatureScheme::Ed25519, &self.private_key_bytes())
+    }
+}
+
+impl KeyPair {
+    pub fn from_bech32(bech32_str: &str) -> Result<Self, CryptoError> {
+        let (scheme, private_key) = decode_private_key(bech32_str)?;
+        KeyPair::from_seed_ref(scheme, &private_key)
+    }
+
+    pub fn to_bech32(&self) -> Result<Bech32PrivateKey, CryptoError> {
+        let private_key = match self {
+            KeyPair::Ed25519(kp) => kp.private_key_bytes(),
+            KeyPair::Secp256k1(kp) => kp.private_key_bytes(),
+            KeyPair::Secp256r1(kp) => kp.private_key_bytes(),
+        };
+        encode_private_key(self.scheme(), &private_key)
+    }
+}
+
+impl Secp256k1Crypto {
+    pub fn to_bech32(&self) -> Result<Bech32PrivateKey, CryptoError> {
+        encode_private_key(SignatureScheme::Secp256k1, &self.private_key_bytes())
+    }
+}
+
+impl Secp256r1Crypto {
+    pub fn to_bech32(&self) -> Result<Bech32PrivateKey, CryptoError> {
+        encode_private_key(SignatureScheme::Secp256r1, &self.private_key_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bech32_private_key_vectors() {
+        let kp = Crypto::from_seed([0; 32]);
+        assert_eq!(
+            kp.to_bech32().unwrap().as_str(),
+            "iotaprivkey1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqgfjx8t"
+        );
+
+        let kp = Secp256k1Crypto::from_seed([1; 32]).unwrap();
+        assert_eq!(
+            kp.to_bech32().unwrap().as_str(),
+            "iotaprivkey1qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszmflqyw"
+        );
+
+        let kp = Secp256r1Crypto::from_seed([1; 32]).unwrap();
+        assert_eq!(
+            kp.to_bech32().unwrap().as_str(),
+            "iotaprivkey1qgqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszupfgp7"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bech32_private_key_roundtrip() {
+        for scheme in [
+            SignatureScheme::Ed25519,
+            SignatureScheme::Secp256k1,
+            SignatureScheme::Secp256r1,
+        ] {
+            let kp = KeyPair::from_seed(scheme, [7; 32]).unwrap();
+            let encoded = kp.to_bech32().unwrap();
+            let decoded = KeyPair::from_bech32(&encoded).unwrap();
+            assert_eq!(decoded.scheme(), scheme);
+            assert_eq!(decoded.public_address(), kp.public_address());
+        }
+
+        let kp = Crypto::from_seed([0; 32]);
+        assert_eq!(
+            Crypto::from_bech32(&kp.to_bech32().unwrap())
+                .unwrap()
+                .public_address(),
+            kp.public_address()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bech32_private_key_errors() {
+        // Wrong checksum
+        assert_eq!(
+            decode_private_key(
+                "iotaprivkey1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqgfjx8q"
+            ),
+            Err(CryptoError::InvalidBech32)
+        );
+        // Wrong prefix
+        assert_eq!(
+            decode_private_key(
+                "suiprivkey1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq509duq"
+            ),
+            Err(CryptoError::InvalidBech32)
+        );
+        // Payload one byte short and one byte long
+        for length in [32, 34] {
+            let hrp = Hrp::parse(IOTA_PRIV_KEY_PREFIX).unwrap();
+            let mut encoded = Bech32PrivateKey::new();
+            bech32::encode_lower_to_fmt::<Bech32, _>(&mut encoded, hrp, &[0u8; 34][..length])
+                .unwrap();
+            assert_eq!(
+                decode_private_key(&encoded),
+                Err(CryptoError::WrongLength {
+                    expected: 33,
+                    actual: length
+                })
+            );
+        }
+        // Secp256k1 key into an Ed25519 Crypto
+        assert_eq!(
+            Crypto::from_bech32(
+                "iotaprivkey1qyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszqgpqyqszmflqyw"
+            )
+            .err(),
+            Some(CryptoError::WrongScheme {
+                expected: SignatureScheme::Ed25519,
+                found: SignatureScheme::Secp256k1
+            })
+        );
+    }
+}
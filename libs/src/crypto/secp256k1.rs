@@ -0,0 +1,192 @@
+use super::{
+    CryptoError, Intent, PublicKeyBytes, SignatureBytes, SignatureScheme, finalize_digest,
+    intent_hasher,
+};
+use crate::address::IotaAddress;
+use crate::bcs_stream::serialize_into;
+use k256::ecdsa::signature::Signer as _;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+pub const SECP256K1_PUBLIC_KEY_LENGTH: usize = 33;
+pub const SECP256K1_SIGNATURE_LENGTH: usize = 64;
+
+pub const SECP256K1_SIGNATURE_SIZE: usize =
+    SECP256K1_SIGNATURE_LENGTH + SECP256K1_PUBLIC_KEY_LENGTH + 1;
+
+pub struct Secp256k1Crypto {
+    verifying_key: k256::ecdsa::VerifyingKey,
+    signing_key: k256::ecdsa::SigningKey,
+}
+
+impl Secp256k1Crypto {
+    /// Fails if the seed is not a valid secp256k1 scalar (zero or >= curve order).
+    /// The seed is wiped after use. Callers should zeroize their own copy.
+    pub fn from_seed(mut seed: [u8; 32]) -> Result<Self, CryptoError> {
+        let crypto = Self::from_seed_ref(&seed);
+        seed.zeroize();
+        crypto
+    }
+
+    /// Borrows the seed, so a `Zeroizing` seed is never copied out of its wrapper.
+    pub fn from_seed_ref(seed: &[u8; 32]) -> Result<Self, CryptoError> {
+        let signing_key =
+            k256::ecdsa::SigningKey::from_slice(seed).map_err(|_| CryptoError::InvalidKey)?;
+        let verifying_key = *signing_key.verifying_key();
+
+        Ok(Self {
+            verifying_key,
+            signing_key,
+        })
+    }
+
+    /// This signs data with the default Intent (3x 0 bytes)
+    pub fn sign(&self, data: &[u8]) -> [u8; SECP256K1_SIGNATURE_SIZE] {
+        self.sign_with_intent(Intent::iota_transaction(), data)
+    }
+
+    pub fn sign_with_intent(&self, intent: Intent, data: &[u8]) -> [u8; SECP256K1_SIGNATURE_SIZE] {
+        let mut hasher = intent_hasher(intent);
+        hasher.update(data);
+        self.sign_hash(&finalize_digest(hasher))
+    }
+
+    pub fn sign_bcs<T: serde::Serialize + ?Sized>(
+        &self,
+        intent: Intent,
+        value: &T,
+    ) -> Result<[u8; SECP256K1_SIGNATURE_SIZE], CryptoError> {
+        let mut hasher = intent_hasher(intent);
+        serialize_into(&mut hasher, value)?;
+        Ok(self.sign_hash(&finalize_digest(hasher)))
+    }
+
+    /// See [`KeyPair::sign_personal_message`](super::KeyPair::sign_personal_message).
+    pub fn sign_personal_message(
+        &self,
+        message: &[u8],
+    ) -> Result<[u8; SECP256K1_SIGNATURE_SIZE], CryptoError> {
+        self.sign_bcs(Intent::personal_message(), message)
+    }
+
+    // The blake2b digest is hashed again with SHA-256 by the ECDSA signer (RFC6979 nonce, low-S),
+    // which matches how IOTA signs and verifies secp256k1 signatures.
+    fn sign_hash(&self, hash: &[u8; 32]) -> [u8; SECP256K1_SIGNATURE_SIZE] {
+        let signature: k256::ecdsa::Signature = self.signing_key.sign(hash);
+
+        let mut result = [0u8; SECP256K1_SIGNATURE_SIZE];
+
+        result[0] = SignatureScheme::Secp256k1.flag();
+        result[1..1 + SECP256K1_SIGNATURE_LENGTH].copy_from_slice(&signature.to_bytes());
+        result[1 + SECP256K1_SIGNATURE_LENGTH..].copy_from_slice(&self.public_key_bytes());
+
+        result
+    }
+
+    /// Compressed SEC1 public key.
+    pub fn public_key_bytes(&self) -> [u8; SECP256K1_PUBLIC_KEY_LENGTH] {
+        let mut bytes = [0u8; SECP256K1_PUBLIC_KEY_LENGTH];
+        bytes.copy_from_slice(self.verifying_key.to_encoded_point(true).as_bytes());
+        bytes
+    }
+
+    pub fn public_address(&self) -> IotaAddress {
+        IotaAddress::from_public_key(SignatureScheme::Secp256k1, &self.public_key_bytes())
+    }
+
+    pub fn verifying_key(&self) -> &k256::ecdsa::VerifyingKey {
+        &self.verifying_key
+    }
+
+    #[cfg(feature = "expose_signing_key")]
+    pub fn signing_key(&self) -> &k256::ecdsa::SigningKey {
+        &self.signing_key
+    }
+
+    pub(crate) fn private_key_bytes(&self) -> Zeroizing<[u8; 32]> {
+        let mut private_key = self.signing_key.to_bytes();
+        let result = Zeroizing::new(private_key.into());
+        private_key[..].zeroize();
+        result
+    }
+}
+
+// The ecdsa signing key wipes itself on drop.
+impl ZeroizeOnDrop for Secp256k1Crypto {}
+
+impl super::Signer for Secp256k1Crypto {
+    type Error = CryptoError;
+
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Secp256k1
+    }
+
+    fn public_key(&self) -> PublicKeyBytes {
+        PublicKeyBytes::from_slice(&self.public_key_bytes()).expect("public key fits")
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<SignatureBytes, CryptoError> {
+        Ok(SignatureBytes::from_slice(&self.sign_hash(digest)).expect("signature fits"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::signature::Verifier;
+
+    #[tokio::test]
+    async fn test_secp256k1_signing() {
+        let kp = Secp256k1Crypto::from_seed([1; 32]).unwrap();
+        let data = b"Hello, World";
+
+        let expected_public_key = [
+            3, 27, 132, 197, 86, 123, 18, 100, 64, 153, 93, 62, 213, 170, 186, 5, 101, 215, 30, 24,
+            52, 96, 72, 25, 255, 156, 23, 245, 233, 213, 221, 7, 143,
+        ];
+
+        let expected_signature = [
+            235, 156, 133, 153, 175, 118, 178, 78, 155, 198, 45, 66, 199, 80, 176, 89, 60, 48, 4,
+            247, 96, 77, 30, 43, 177, 131, 11, 180, 213, 15, 78, 164, 119, 224, 117, 128, 67, 94,
+            229, 252, 77, 19, 142, 12, 125, 203, 40, 1, 217, 205, 1, 169, 254, 243, 119, 242, 8,
+            111, 215, 132, 223, 56, 39, 155,
+        ];
+
+        let expected_personal_message_signature = [
+            194, 159, 207, 141, 88, 118, 146, 105, 66, 145, 105, 117, 230, 100, 219, 162, 39, 106,
+            152, 43, 62, 64, 3, 182, 204, 201, 31, 217, 245, 84, 183, 26, 116, 234, 113, 207, 221,
+            106, 234, 192, 212, 225, 79, 147, 42, 139, 242, 145, 179, 52, 132, 3, 178, 58, 231, 98,
+            138, 68, 220, 18, 82, 167, 170, 226,
+        ];
+
+        assert_eq!(kp.public_key_bytes(), expected_public_key);
+        assert_eq!(
+            kp.public_address().to_hex().as_str(),
+            "f87edcc926ae7dded7f91ffddcb0ba6c9e3373946e89ec47e478c1bca90c750d"
+        );
+
+        let sig = kp.sign(data);
+        assert_eq!(sig[0], 0x01);
+        assert_eq!(sig[1..65], expected_signature);
+        assert_eq!(sig[65..], expected_public_key);
+
+        let sig = kp.sign_with_intent(Intent::personal_message(), data);
+        assert_eq!(sig[1..65], expected_personal_message_signature);
+
+        let digest = intent_hasher(Intent::personal_message())
+            .update(data)
+            .finalize();
+        let signature = k256::ecdsa::Signature::from_slice(&sig[1..65]).unwrap();
+        assert!(signature.normalize_s().is_none());
+        assert!(
+            kp.verifying_key()
+                .verify(digest.as_bytes(), &signature)
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_secp256k1_invalid_seed() {
+        assert!(Secp256k1Crypto::from_seed([0; 32]).is_err());
+        assert!(Secp256k1Crypto::from_seed([0xff; 32]).is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use super::{
+    CryptoError, Intent, PublicKeyBytes, SignatureBytes, SignatureScheme, finalize_digest,
+    intent_hasher,
+};
+use crate::address::IotaAddress;
+use crate::bcs_stream::serialize_into;
+use p256::ecdsa::signature::Signer as _;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+pub const SECP256R1_PUBLIC_KEY_LENGTH: usize = 33;
+pub const SECP256R1_SIGNATURE_LENGTH: usize = 64;
+
+pub const SECP256R1_SIGNATURE_SIZE: usize =
+    SECP256R1_SIGNATURE_LENGTH + SECP256R1_PUBLIC_KEY_LENGTH + 1;
+
+/// Software secp256r1 (P-256) keypair. Produces the same signatures a P-256 secure element
+/// would, so firmware can be developed against this and swapped to hardware later.
+pub struct Secp256r1Crypto {
+    verifying_key: p256::ecdsa::VerifyingKey,
+    signing_key: p256::ecdsa::SigningKey,
+}
+
+impl Secp256r1Crypto {
+    /// Fails if the seed is not a valid P-256 scalar (zero or >= curve order).
+    /// The seed is wiped after use. Callers should zeroize their own copy.
+    pub fn from_seed(mut seed: [u8; 32]) -> Result<Self, CryptoError> {
+        let crypto = Self::from_seed_ref(&seed);
+        seed.zeroize();
+        crypto
+    }
+
+    /// Borrows the seed, so a `Zeroizing` seed is never copied out of its wrapper.
+    pub fn from_seed_ref(seed: &[u8; 32]) -> Result<Self, CryptoError> {
+        let signing_key =
+            p256::ecdsa::SigningKey::from_slice(seed).map_err(|_| CryptoError::InvalidKey)?;
+        let verifying_key = *signing_key.verifying_key();
+
+        Ok(Self {
+            verifying_key,
+            signing_key,
+        })
+    }
+
+    /// This signs data with the default Intent (3x 0 bytes)
+    pub fn sign(&self, data: &[u8]) -> [u8; SECP256R1_SIGNATURE_SIZE] {
+        self.sign_with_intent(Intent::iota_transaction(), data)
+    }
+
+    pub fn sign_with_intent(&self, intent: Intent, data: &[u8]) -> [u8; SECP256R1_SIGNATURE_SIZE] {
+        let mut hasher = intent_hasher(intent);
+        hasher.update(data);
+        self.sign_hash(&finalize_digest(hasher))
+    }
+
+    pub fn sign_bcs<T: serde::Serialize + ?Sized>(
+        &self,
+        intent: Intent,
+        value: &T,
+    ) -> Result<[u8; SECP256R1_SIGNATURE_SIZE], CryptoError> {
+        let mut hasher = intent_hasher(intent);
+        serialize_into(&mut hasher, value)?;
+        Ok(self.sign_hash(&finalize_digest(hasher)))
+    }
+
+    /// See [`KeyPair::sign_personal_message`](super::KeyPair::sign_personal_message).
+    pub fn sign_personal_message(
+        &self,
+        message: &[u8],
+    ) -> Result<[u8; SECP256R1_SIGNATURE_SIZE], CryptoError> {
+        self.sign_bcs(Intent::personal_message(), message)
+    }
+
+    // Unlike k256, p256 does not normalize S. IOTA rejects high-S signatures, so flip it here.
+    fn sign_hash(&self, hash: &[u8; 32]) -> [u8; SECP256R1_SIGNATURE_SIZE] {
+        let signature: p256::ecdsa::Signature = self.signing_key.sign(hash);
+        let signature = signature.normalize_s().unwrap_or(signature);
+
+        let mut result = [0u8; SECP256R1_SIGNATURE_SIZE];
+
+        result[0] = SignatureScheme::Secp256r1.flag();
+        result[1..1 + SECP256R1_SIGNATURE_LENGTH].copy_from_slice(&signature.to_bytes());
+        result[1 + SECP256R1_SIGNATURE_LENGTH..].copy_from_slice(&self.public_key_bytes());
+
+        result
+    }
+
+    /// Compressed SEC1 public key.
+    pub fn public_key_bytes(&self) -> [u8; SECP256R1_PUBLIC_KEY_LENGTH] {
+        let mut bytes = [0u8; SECP256R1_PUBLIC_KEY_LENGTH];
+        bytes.copy_from_slice(self.verifying_key.to_encoded_point(true).as_bytes());
+        bytes
+    }
+
+    pub fn public_address(&self) -> IotaAddress {
+        IotaAddress::from_public_key(SignatureScheme::Secp256r1, &self.public_key_bytes())
+    }
+
+    pub fn verifying_key(&self) -> &p256::ecdsa::VerifyingKey {
+        &self.verifying_key
+    }
+
+    #[cfg(feature = "expose_signing_key")]
+    pub fn signing_key(&self) -> &p256::ecdsa::SigningKey {
+        &self.signing_key
+    }
+
+    pub(crate) fn private_key_bytes(&self) -> Zeroizing<[u8; 32]> {
+        let mut private_key = self.signing_key.to_bytes();
+        let result = Zeroizing::new(private_key.into());
+        private_key[..].zeroize();
+        result
+    }
+}
+
+// The ecdsa signing key wipes itself on drop.
+impl ZeroizeOnDrop for Secp256r1Crypto {}
+
+impl super::Signer for Secp256r1Crypto {
+    type Error = CryptoError;
+
+    fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::Secp256r1
+    }
+
+    fn public_key(&self) -> PublicKeyBytes {
+        PublicKeyBytes::from_slice(&self.public_key_bytes()).expect("public key fits")
+    }
+
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<SignatureBytes, CryptoError> {
+        Ok(SignatureBytes::from_slice(&self.sign_hash(digest)).expect("signature fits"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p256::ecdsa::signature::Verifier;
+
+    #[tokio::test]
+    async fn test_secp256r1_signing() {
+        let kp = Secp256r1Crypto::from_seed([1; 32]).unwrap();
+        let data = b"Hello, World";
+
+        let expected_public_key = [
+            2, 111, 240, 59, 148, 146, 65, 206, 29, 173, 212, 53, 25, 230, 150, 14, 10, 133, 180,
+            26, 105, 160, 92, 50, 129, 3, 170, 43, 206, 21, 148, 202, 22,
+        ];
+
+        let expected_signature = [
+            71, 255, 149, 70, 232, 157, 30, 61, 50, 87, 197, 78, 137, 88, 111, 112, 112, 100, 44,
+            10, 105, 106, 239, 46, 165, 8, 118, 53, 221, 116, 103, 251, 28, 118, 26, 169, 197, 36,
+            140, 253, 129, 126, 219, 215, 85, 16, 74, 138, 126, 69, 32, 134, 12, 3, 45, 192, 0,
+            231, 219, 16, 222, 105, 71, 33,
+        ];
+
+        let expected_personal_message_signature = [
+            209, 185, 157, 206, 160, 67, 139, 100, 24, 60, 186, 72, 192, 131, 184, 175, 71, 241,
+            37, 154, 192, 177, 196, 148, 214, 33, 86, 93, 187, 61, 200, 123, 5, 220, 12, 7, 184,
+            119, 229, 135, 164, 74, 245, 217, 160, 171, 172, 79, 105, 249, 221, 223, 164, 113, 89,
+            85, 232, 74, 249, 58, 224, 36, 0, 126,
+        ];
+
+        assert_eq!(kp.public_key_bytes(), expected_public_key);
+        assert_eq!(
+            kp.public_address().to_hex().as_str(),
+            "575dc0072a3309367790cb4415ddc87df5ffa4360ccd2c29f7ec0515026cc0e1"
+        );
+
+        let sig = kp.sign(data);
+        assert_eq!(sig[0], 0x02);
+        assert_eq!(sig[1..65], expected_signature);
+        assert_eq!(sig[65..], expected_public_key);
+
+        let sig = kp.sign_with_intent(Intent::personal_message(), data);
+        assert_eq!(sig[1..65], expected_personal_message_signature);
+    }
+
+    #[tokio::test]
+    async fn test_secp256r1_low_s() {
+        let kp = Secp256r1Crypto::from_seed([1; 32]).unwrap();
+
+        // The raw RFC6979 signature over this payload has a high S value.
+        let data = b"reading 1";
+        let expected_signature = [
+            215, 103, 221, 177, 140, 120, 20, 206, 207, 213, 184, 70, 22, 161, 19, 173, 182, 90,
+            217, 240, 115, 19, 145, 113, 68, 11, 85, 214, 230, 242, 105, 196, 54, 75, 109, 94, 162,
+            17, 112, 78, 248, 154, 129, 184, 173, 102, 34, 246, 241, 231, 67, 87, 240, 142, 2, 240,
+            177, 136, 91, 170, 166, 141, 36, 145,
+        ];
+
+        let sig = kp.sign(data);
+        assert_eq!(sig[1..65], expected_signature);
+
+        let digest = intent_hasher(Intent::iota_transaction())
+            .update(data)
+            .finalize();
+        let signature = p256::ecdsa::Signature::from_slice(&sig[1..65]).unwrap();
+        assert!(signature.normalize_s().is_none());
+        assert!(
+            kp.verifying_key()
+                .verify(digest.as_bytes(), &signature)
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_secp256r1_invalid_seed() {
+        assert!(Secp256r1Crypto::from_seed([0; 32]).is_err());
+        assert!(Secp256r1Crypto::from_seed([0xff; 32]).is_err());
+    }
+}
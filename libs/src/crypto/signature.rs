@@ -0,0 +1,321 @@
+use super::{
+    CryptoError, Intent, MAX_SERIALIZED_SIGNATURE_SIZE, SignatureBytes, SignatureScheme,
+    intent_hasher,
+};
+use crate::address::IotaAddress;
+use crate::bcs_stream::serialize_into;
+use base64::prelude::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// All supported schemes use 64 byte signatures.
+const SIGNATURE_LENGTH: usize = 64;
+
+/// A parsed `flag || signature || public_key` blob, as produced by the signers in this module
+/// and as sent in the gas station `user_sig` field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializedSignature {
+    bytes: SignatureBytes,
+}
+
+impl SerializedSignature {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        let flag = *bytes.first().ok_or(CryptoError::WrongLength {
+            expected: 1,
+            actual: 0,
+        })?;
+        let scheme = SignatureScheme::from_flag(flag)?;
+
+        if bytes.len() != scheme.serialized_signature_size() {
+            return Err(CryptoError::WrongLength {
+                expected: scheme.serialized_signature_size(),
+                actual: bytes.len(),
+            });
+        }
+
+        Ok(Self {
+            bytes: SignatureBytes::from_slice(bytes).map_err(|_| CryptoError::InvalidSignature)?,
+        })
+    }
+
+    pub fn from_base64(base64_str: &str) -> Result<Self, CryptoError> {
+        // Base64 decoding needs some slack for padding.
+        let mut buf = [0u8; MAX_SERIALIZED_SIGNATURE_SIZE + 3];
+        let size = BASE64_STANDARD
+            .decode_slice(base64_str, &mut buf)
+            .map_err(|_| CryptoError::InvalidBase64)?;
+
+        Self::from_bytes(&buf[..size])
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn as_base64_string<const N: usize>(&self) -> Result<heapless::String<N>, CryptoError> {
+        let mut buf = [0u8; N];
+        let size = BASE64_STANDARD
+            .encode_slice(&self.bytes, &mut buf)
+            .map_err(|_| CryptoError::InvalidBase64)?;
+
+        let vec_data =
+            heapless::Vec::from_slice(&buf[..size]).map_err(|_| CryptoError::InvalidBase64)?;
+        heapless::String::from_utf8(vec_data).map_err(|_| CryptoError::InvalidBase64)
+    }
+
+    pub fn scheme(&self) -> SignatureScheme {
+        SignatureScheme::from_flag(self.bytes[0]).expect("flag is validated on construction")
+    }
+
+    pub fn signature_bytes(&self) -> &[u8] {
+        &self.bytes[1..1 + SIGNATURE_LENGTH]
+    }
+
+    pub fn public_key_bytes(&self) -> &[u8] {
+        &self.bytes[1 + SIGNATURE_LENGTH..]
+    }
+
+    /// The address of the key embedded in the signature. Only meaningful after `verify`.
+    pub fn signer_address(&self) -> IotaAddress {
+        IotaAddress::from_public_key(self.scheme(), self.public_key_bytes())
+    }
+
+    /// Verifies the signature over `intent || message` and returns the signer address.
+    /// Callers must compare the address with the expected signer (e.g. tx sender or sponsor).
+    pub fn verify(&self, message: &[u8], intent: Intent) -> Result<IotaAddress, CryptoError> {
+        let mut hasher = intent_hasher(intent);
+        hasher.update(message);
+        self.verify_digest(hasher.finalize().as_bytes())?;
+        Ok(self.signer_address())
+    }
+
+    /// Same as `verify`, but streams the BCS encoding of `value` instead of taking raw bytes.
+    pub fn verify_bcs<T: Serialize + ?Sized>(
+        &self,
+        value: &T,
+        intent: Intent,
+    ) -> Result<IotaAddress, CryptoError> {
+        let mut hasher = intent_hasher(intent);
+        serialize_into(&mut hasher, value)?;
+        self.verify_digest(hasher.finalize().as_bytes())?;
+        Ok(self.signer_address())
+    }
+
+    /// Verifies a signature produced by `sign_personal_message` or a wallet's `signPersonalMessage`.
+    pub fn verify_personal_message(&self, message: &[u8]) -> Result<IotaAddress, CryptoError> {
+        self.verify_bcs(message, Intent::personal_message())
+    }
+
+    pub(super) fn verify_digest(&self, digest: &[u8]) -> Result<(), CryptoError> {
+        match self.scheme() {
+            SignatureScheme::Ed25519 => {
+                use ed25519_dalek::Verifier;
+
+                let public_key: &[u8; ed25519_dalek::PUBLIC_KEY_LENGTH] = self
+                    .public_key_bytes()
+                    .try_into()
+                    .map_err(|_| CryptoError::InvalidKey)?;
+                let verifying_key = ed25519_dalek::VerifyingKey::from_bytes(public_key)
+                    .map_err(|_| CryptoError::InvalidKey)?;
+                let signature = ed25519_dalek::Signature::from_slice(self.signature_bytes())
+                    .map_err(|_| CryptoError::InvalidSignature)?;
+
+                verifying_key
+                    .verify(digest, &signature)
+                    .map_err(|_| CryptoError::InvalidSignature)
+            }
+            SignatureScheme::Secp256k1 => {
+                use k256::ecdsa::signature::Verifier;
+
+                let verifying_key =
+                    k256::ecdsa::VerifyingKey::from_sec1_bytes(self.public_key_bytes())
+                        .map_err(|_| CryptoError::InvalidKey)?;
+                let signature = k256::ecdsa::Signature::from_slice(self.signature_bytes())
+                    .map_err(|_| CryptoError::InvalidSignature)?;
+
+                // k256 already rejects high-S signatures.
+                verifying_key
+                    .verify(digest, &signature)
+                    .map_err(|_| CryptoError::InvalidSignature)
+            }
+            SignatureScheme::Secp256r1 => {
+                use p256::ecdsa::signature::Verifier;
+
+                let verifying_key =
+                    p256::ecdsa::VerifyingKey::from_sec1_bytes(self.public_key_bytes())
+                        .map_err(|_| CryptoError::InvalidKey)?;
+                let signature = p256::ecdsa::Signature::from_slice(self.signature_bytes())
+                    .map_err(|_| CryptoError::InvalidSignature)?;
+
+                if signature.normalize_s().is_some() {
+                    return Err(CryptoError::InvalidSignature);
+                }
+
+                verifying_key
+                    .verify(digest, &signature)
+                    .map_err(|_| CryptoError::InvalidSignature)
+            }
+        }
+    }
+}
+
+impl TryFrom<&[u8]> for SerializedSignature {
+    type Error = CryptoError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl Serialize for SerializedSignature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let base64_str: heapless::String<256> = self
+            .as_base64_string()
+            .map_err(|_| serde::ser::Error::custom("Failed to encode signature"))?;
+
+        serializer.serialize_str(base64_str.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for SerializedSignature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let base64_str = heapless::String::<256>::deserialize(deserializer)?;
+
+        SerializedSignature::from_base64(&base64_str)
+            .map_err(|_| serde::de::Error::custom("Invalid serialized signature"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::{Crypto, KeyPair};
+
+    #[tokio::test]
+    async fn test_verify_all_schemes() {
+        let data = b"Hello, World";
+
+        for scheme in [
+            SignatureScheme::Ed25519,
+            SignatureScheme::Secp256k1,
+            SignatureScheme::Secp256r1,
+        ] {
+            let kp = KeyPair::from_seed(scheme, [1; 32]).unwrap();
+            let sig = SerializedSignature::from_bytes(&kp.sign(data)).unwrap();
+
+            assert_eq!(sig.scheme(), scheme);
+            assert_eq!(sig.public_key_bytes(), kp.public_key_bytes().as_slice());
+            assert_eq!(
+                sig.verify(data, Intent::iota_transaction()).unwrap(),
+                kp.public_address()
+            );
+            assert_eq!(
+                sig.verify(data, Intent::personal_message()),
+                Err(CryptoError::InvalidSignature)
+            );
+            assert_eq!(
+                sig.verify(b"Hello, World!", Intent::iota_transaction()),
+                Err(CryptoError::InvalidSignature)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_personal_message() {
+        let message = b"Hello, World";
+
+        // Independently computed over blake2b([3, 0, 0] || bcs(vector<u8>)),
+        // the same encoding wallets and the CLI use for personal messages.
+        let ed25519 = "AJ1ikTF3rimOITnSJuiSHd2yTe67yllUwJDPHXZq8X2mMF6Hl4iesr3tpoqhmaM2UWOHv8s5FqX6gOSkzGZ+KwU7aie8zrakLWKjqNAqbw1zZTIVdx3iQ6Y6wEihi1naKQ==";
+        let secp256k1 = "AbwQuMZCZjj6pg1JmoXJN56wOrqyehf5Nqgsi/XraS1JOV+cvX9AeBUSfnkSRuNIt7B/enfaYHMye9fhcK4VeX8DG4TFVnsSZECZXT7VqroFZdceGDRgSBn/nBf16dXdB48=";
+
+        let kp = Crypto::from_seed([0; 32]);
+        let sig =
+            SerializedSignature::from_bytes(&kp.sign_personal_message(message).unwrap()).unwrap();
+        assert_eq!(sig.as_base64_string::<256>().unwrap(), ed25519);
+        assert_eq!(
+            sig.verify_personal_message(message).unwrap(),
+            kp.public_address()
+        );
+
+        let kp = KeyPair::from_seed(SignatureScheme::Secp256k1, [1; 32]).unwrap();
+        let sig =
+            SerializedSignature::from_bytes(&kp.sign_personal_message(message).unwrap()).unwrap();
+        assert_eq!(sig.as_base64_string::<256>().unwrap(), secp256k1);
+        assert_eq!(
+            crate::crypto::Signer::sign_personal_message(&kp, message)
+                .await
+                .unwrap(),
+            sig.as_bytes()
+        );
+
+        let sig = SerializedSignature::from_base64(secp256k1).unwrap();
+        assert_eq!(
+            sig.verify_personal_message(message).unwrap(),
+            kp.public_address()
+        );
+        // The raw, unwrapped message must not verify.
+        assert_eq!(
+            sig.verify(message, Intent::personal_message()),
+            Err(CryptoError::InvalidSignature)
+        );
+        assert_eq!(
+            sig.verify_personal_message(b"Hello, World!"),
+            Err(CryptoError::InvalidSignature)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_parse_serialized_signature() {
+        let kp = Crypto::from_seed([0; 32]);
+        let sig_bytes = kp.sign(b"data");
+
+        let base64_str: heapless::String<256> = SerializedSignature::from_bytes(&sig_bytes)
+            .unwrap()
+            .as_base64_string()
+            .unwrap();
+        let sig = SerializedSignature::from_base64(&base64_str).unwrap();
+        assert_eq!(sig.as_bytes(), sig_bytes);
+        assert_eq!(sig.scheme(), SignatureScheme::Ed25519);
+
+        let payload = alloc::vec![42u8; 8000];
+        let sig = SerializedSignature::from_bytes(
+            &kp.sign_bcs(Intent::personal_message(), &payload).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            sig.verify_bcs(&payload, Intent::personal_message())
+                .unwrap(),
+            kp.public_address()
+        );
+
+        let mut tampered = sig_bytes;
+        tampered[10] ^= 1;
+        let sig = SerializedSignature::from_bytes(&tampered).unwrap();
+        assert_eq!(
+            sig.verify(b"data", Intent::iota_transaction()),
+            Err(CryptoError::InvalidSignature)
+        );
+
+        assert_eq!(
+            SerializedSignature::from_bytes(&sig_bytes[..96]),
+            Err(CryptoError::WrongLength {
+                expected: 97,
+                actual: 96
+            })
+        );
+        assert_eq!(
+            SerializedSignature::from_bytes(&[9; 97]),
+            Err(CryptoError::UnknownScheme(9))
+        );
+        assert_eq!(
+            SerializedSignature::from_base64("not base64!"),
+            Err(CryptoError::InvalidBase64)
+        );
+    }
+}
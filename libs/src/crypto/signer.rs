@@ -0,0 +1,65 @@
+use super::{
+    CryptoError, Intent, MAX_PUBLIC_KEY_LENGTH, SignatureBytes, SignatureScheme, finalize_digest,
+    intent_hasher,
+};
+use crate::address::IotaAddress;
+use crate::base_types::SignableTransaction;
+use crate::bcs_stream::serialize_into;
+
+pub type PublicKeyBytes = heapless::Vec<u8, MAX_PUBLIC_KEY_LENGTH>;
+
+/// Anything that can produce IOTA user signatures.
+///
+/// Implemented for the in-memory keys in this module. External signers (HSMs, secure elements,
+/// remote signers) only have to provide the public key and sign a 32-byte blake2b digest.
+#[allow(async_fn_in_trait)]
+pub trait Signer {
+    type Error: core::fmt::Debug + From<CryptoError>;
+
+    fn scheme(&self) -> SignatureScheme;
+
+    fn public_key(&self) -> PublicKeyBytes;
+
+    fn address(&self) -> IotaAddress {
+        IotaAddress::from_public_key(self.scheme(), &self.public_key())
+    }
+
+    /// Signs the blake2b digest of an intent message.
+    /// Returns the serialized `flag || signature || public_key`.
+    async fn sign_digest(&self, digest: &[u8; 32]) -> Result<SignatureBytes, Self::Error>;
+
+    async fn sign_intent_message(
+        &self,
+        intent: Intent,
+        message: &[u8],
+    ) -> Result<SignatureBytes, Self::Error> {
+        let mut hasher = intent_hasher(intent);
+        hasher.update(message);
+        self.sign_digest(&finalize_digest(hasher)).await
+    }
+
+    /// Signs the BCS encoding of `value` under `intent` without buffering the serialized bytes.
+    async fn sign_bcs<T: serde::Serialize + ?Sized>(
+        &self,
+        intent: Intent,
+        value: &T,
+    ) -> Result<SignatureBytes, Self::Error> {
+        let mut hasher = intent_hasher(intent);
+        serialize_into(&mut hasher, value).map_err(CryptoError::from)?;
+        self.sign_digest(&finalize_digest(hasher)).await
+    }
+
+    /// Signs `message` as BCS `vector<u8>` under the personal message intent, for proving key
+    /// ownership off-chain. Compatible with wallet `signPersonalMessage`.
+    async fn sign_personal_message(&self, message: &[u8]) -> Result<SignatureBytes, Self::Error> {
+        self.sign_bcs(Intent::personal_message(), message).await
+    }
+
+    /// Accepts both `TransactionData` and the borrowed `TransactionDataRef`.
+    async fn sign_transaction<T: SignableTransaction + ?Sized>(
+        &self,
+        tx: &T,
+    ) -> Result<SignatureBytes, Self::Error> {
+        self.sign_bcs(Intent::iota_transaction(), tx).await
+    }
+}
@@ -1,4 +1,7 @@
+use crate::bcs_stream::{BcsStreamError, BcsWrite, serialize_into, serialized_size, to_slice};
+use crate::crypto::{MAX_MULTISIG_SIZE, MAX_SERIALIZED_SIGNATURE_SIZE};
 use base64::prelude::*;
+use core::fmt::{Display, Formatter};
 use heapless::{String, Vec};
 use serde::{Serialize, Serializer};
 
@@ -12,19 +15,26 @@ impl<T: serde::Serialize> BcsData<T> {
         Self { data }
     }
 
-    pub fn as_bcs_bytes(&self) -> Result<alloc::vec::Vec<u8>, bcs::Error> {
-        bcs::to_bytes(&self.data)
+    pub fn bcs_size(&self) -> Result<usize, EncodingError> {
+        serialized_size(&self.data).map_err(EncodingError::from)
     }
 
-    pub fn as_base64_string<const N: usize>(&self) -> Result<String<N>, EncodingError> {
-        let bcs_bytes = self
-            .as_bcs_bytes()
-            .map_err(|_| EncodingError::SerializationFailed)?;
+    /// Writes the BCS bytes into `buf` and returns their length.
+    pub fn write_bcs_bytes(&self, buf: &mut [u8]) -> Result<usize, EncodingError> {
+        to_slice(buf, &self.data).map_err(EncodingError::from)
+    }
 
+    /// The BCS bytes are encoded while they are serialized, so only the output is buffered.
+    pub fn as_base64_string<const N: usize>(&self) -> Result<String<N>, EncodingError> {
         let mut buf = [0u8; N];
-        let size = BASE64_STANDARD
-            .encode_slice(&bcs_bytes, &mut buf)
-            .map_err(|_| EncodingError::TooLong)?;
+        let mut writer = Base64Writer {
+            out: &mut buf,
+            len: 0,
+            pending: [0; 3],
+            pending_len: 0,
+        };
+        serialize_into(&mut writer, &self.data)?;
+        let size = writer.finish()?;
 
         let vec_data = Vec::from_slice(&buf[..size]).map_err(|_| EncodingError::InvalidData)?;
         let result = String::from_utf8(vec_data).map_err(|_| EncodingError::TooLong)?;
@@ -46,20 +56,72 @@ impl<T: serde::Serialize> Serialize for BcsData<T> {
     }
 }
 
+/// Base64 encodes a byte stream in 3 byte groups, carrying incomplete groups to the next write.
+struct Base64Writer<'a> {
+    out: &'a mut [u8],
+    len: usize,
+    pending: [u8; 3],
+    pending_len: usize,
+}
+
+impl Base64Writer<'_> {
+    fn encode(&mut self, bytes: &[u8]) -> Result<(), BcsStreamError> {
+        self.len += BASE64_STANDARD
+            .encode_slice(bytes, &mut self.out[self.len..])
+            .map_err(|_| BcsStreamError::BufferTooSmall)?;
+        Ok(())
+    }
+
+    /// Encodes the last incomplete group with padding, returns the encoded length.
+    fn finish(mut self) -> Result<usize, BcsStreamError> {
+        let pending = self.pending;
+        self.encode(&pending[..self.pending_len])?;
+        Ok(self.len)
+    }
+}
+
+impl BcsWrite for Base64Writer<'_> {
+    fn write_bytes(&mut self, mut bytes: &[u8]) -> Result<(), BcsStreamError> {
+        if self.pending_len > 0 {
+            let take = (3 - self.pending_len).min(bytes.len());
+            self.pending[self.pending_len..self.pending_len + take].copy_from_slice(&bytes[..take]);
+            self.pending_len += take;
+            bytes = &bytes[take..];
+            if self.pending_len < 3 {
+                return Ok(());
+            }
+            let pending = self.pending;
+            self.encode(&pending)?;
+            self.pending_len = 0;
+        }
+
+        let whole = bytes.len() - bytes.len() % 3;
+        self.encode(&bytes[..whole])?;
+        let rest = &bytes[whole..];
+        self.pending[..rest.len()].copy_from_slice(rest);
+        self.pending_len = rest.len();
+        Ok(())
+    }
+}
+
+/// A serialized signature, sized for single key signatures by default so requests stay small
+/// on the stack. Use `MultiSigBase64Signature` for multisig.
 #[derive(Debug, Clone)]
-pub struct Base64Signature {
-    bytes: Vec<u8, 128>,
+pub struct Base64Signature<const N: usize = MAX_SERIALIZED_SIGNATURE_SIZE> {
+    bytes: Vec<u8, N>,
 }
 
-impl Base64Signature {
+pub type MultiSigBase64Signature = Base64Signature<MAX_MULTISIG_SIZE>;
+
+impl<const N: usize> Base64Signature<N> {
     pub fn new(signature_bytes: &[u8]) -> Result<Self, EncodingError> {
         let bytes = Vec::from_slice(signature_bytes).map_err(|_| EncodingError::TooLong)?;
 
         Ok(Self { bytes })
     }
 
-    pub fn as_base64_string<const N: usize>(&self) -> Result<String<N>, EncodingError> {
-        let mut buf = [0u8; N];
+    pub fn as_base64_string<const M: usize>(&self) -> Result<String<M>, EncodingError> {
+        let mut buf = [0u8; M];
         let size = BASE64_STANDARD
             .encode_slice(&self.bytes, &mut buf)
             .map_err(|_| EncodingError::TooLong)?;
@@ -71,16 +133,27 @@ impl Base64Signature {
     }
 }
 
-impl Serialize for Base64Signature {
+// Encodes 3 bytes at a time, so formatting needs no buffer for the whole string.
+impl<const N: usize> Display for Base64Signature<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut group = [0u8; 4];
+        for chunk in self.bytes.chunks(3) {
+            let size = BASE64_STANDARD
+                .encode_slice(chunk, &mut group)
+                .map_err(|_| core::fmt::Error)?;
+            let group = core::str::from_utf8(&group[..size]).map_err(|_| core::fmt::Error)?;
+            f.write_str(group)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const N: usize> Serialize for Base64Signature<N> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let base64_str: String<512> = self
-            .as_base64_string()
-            .map_err(|_| serde::ser::Error::custom("Failed to encode signature"))?;
-
-        serializer.serialize_str(base64_str.as_str())
+        serializer.collect_str(self)
     }
 }
 
@@ -90,3 +163,65 @@ pub enum EncodingError {
     TooLong,
     InvalidData,
 }
+
+impl From<BcsStreamError> for EncodingError {
+    fn from(err: BcsStreamError) -> Self {
+        match err {
+            BcsStreamError::BufferTooSmall => EncodingError::TooLong,
+            _ => EncodingError::SerializationFailed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_bcs_data_base64() {
+        // Writes of every length modulo 3, across the group boundaries.
+        for len in 0..10 {
+            let value = (7u8, alloc::vec![1u8; len], "ISS", 12345u32);
+            let bcs_bytes = bcs::to_bytes(&value).unwrap();
+            let data = BcsData::new(value);
+
+            let base64: String<128> = data.as_base64_string().unwrap();
+            let mut expected = [0u8; 128];
+            let expected_size = BASE64_STANDARD
+                .encode_slice(&bcs_bytes, &mut expected)
+                .unwrap();
+            assert_eq!(base64.as_bytes(), &expected[..expected_size]);
+
+            assert_eq!(data.bcs_size().unwrap(), bcs_bytes.len());
+            let mut buf = [0u8; 64];
+            let size = data.write_bcs_bytes(&mut buf).unwrap();
+            assert_eq!(&buf[..size], bcs_bytes.as_slice());
+        }
+
+        let data = BcsData::new([0u8; 12]);
+        assert!(matches!(
+            data.as_base64_string::<15>(),
+            Err(EncodingError::TooLong)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_base64_signature_serialize() {
+        for len in [96, 97, 98] {
+            let bytes = alloc::vec![5u8; len];
+            let signature: Base64Signature = Base64Signature::new(&bytes).unwrap();
+            let expected: String<136> = signature.as_base64_string().unwrap();
+
+            let mut buf = [0u8; 256];
+            let size = serde_json_core::to_slice(&signature, &mut buf).unwrap();
+            assert_eq!(&buf[1..size - 1], expected.as_bytes());
+        }
+
+        let multisig = [5u8; MAX_MULTISIG_SIZE];
+        assert!(Base64Signature::<MAX_SERIALIZED_SIGNATURE_SIZE>::new(&multisig).is_err());
+        let signature = MultiSigBase64Signature::new(&multisig).unwrap();
+        let mut buf = [0u8; 2048];
+        let size = serde_json_core::to_slice(&signature, &mut buf).unwrap();
+        assert_eq!(size, MAX_MULTISIG_SIZE.div_ceil(3) * 4 + 2);
+    }
+}
@@ -1,160 +1,22 @@
 use core::fmt::{Debug, Write};
 use serde_bytes::deserialize;
 
+use crate::address::IotaAddress;
+use crate::base_types::{Digest, ObjectRef, SignableTransaction};
+use crate::crypto::{MAX_SERIALIZED_SIGNATURE_SIZE, Signer};
 use crate::encoding::{Base64Signature, BcsData};
 use crate::json_client::{ClientError, JsonClient};
-use crate::transaction_types;
-use crate::transaction_types::IOTA_ADDRESS_LENGTH;
-use defmt::Format;
 use embedded_nal_async::{Dns, TcpConnect};
 use heapless::{String, Vec};
 use reqwless::client::HttpClient;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
-
-#[derive(Debug)]
-pub enum ObjectIDError {
-    InvalidHex,
-    TooLong,
-    WrongLength { expected: usize, actual: usize },
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Format)]
-pub struct ObjectID([u8; IOTA_ADDRESS_LENGTH]);
-
-impl Serialize for ObjectID {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let mut str = heapless::String::<128>::new();
-        write!(str, "0x{}", hex::encode(self.0))
-            .map_err(|_| serde::ser::Error::custom("Invalid hex"))?;
-        serializer.serialize_str(&str)
-    }
-}
-
-impl<'de> Deserialize<'de> for ObjectID {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let hex_str = heapless::String::<128>::deserialize(deserializer)?;
-        let hex_str = hex_str.strip_prefix("0x").unwrap_or(&hex_str);
-        let mut buffer = [0u8; IOTA_ADDRESS_LENGTH];
-
-        hex::decode_to_slice(hex_str, &mut buffer)
-            .map_err(|_| serde::de::Error::custom("Invalid hex"))?;
-
-        Ok(ObjectID(buffer))
-    }
-}
-
-impl ObjectID {
-    pub fn from_hex(hex_str: &str) -> Result<Self, ObjectIDError> {
-        let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
-        let mut buf = [0u8; IOTA_ADDRESS_LENGTH];
-        hex::decode_to_slice(hex_str, &mut buf).map_err(|_| ObjectIDError::InvalidHex)?;
-        Ok(ObjectID(buf))
-    }
-
-    pub fn as_bytes(&self) -> [u8; IOTA_ADDRESS_LENGTH] {
-        self.0
-    }
-
-    pub fn as_tx_object_id(&self) -> transaction_types::ObjectID {
-        transaction_types::ObjectID::new(self.as_bytes())
-    }
-}
-
-impl From<ObjectID> for transaction_types::ObjectID {
-    fn from(val: ObjectID) -> Self {
-        val.as_tx_object_id()
-    }
-}
-
-#[derive(Debug)]
-pub enum DigestError {
-    InvalidBase58,
-    TooLong,
-    WrongLength,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq, Format)]
-pub struct Digest([u8; IOTA_ADDRESS_LENGTH]);
-
-impl Digest {
-    pub fn from_base58(b58_str: &str) -> Result<Self, DigestError> {
-        let mut buffer = [0u8; IOTA_ADDRESS_LENGTH];
-        let length = bs58::decode(b58_str)
-            .onto(&mut buffer)
-            .map_err(|_| DigestError::InvalidBase58)?;
-
-        if length != IOTA_ADDRESS_LENGTH {
-            return Err(DigestError::WrongLength);
-        }
-
-        Ok(Self(buffer))
-    }
-
-    pub fn as_bytes(&self) -> [u8; IOTA_ADDRESS_LENGTH] {
-        self.0
-    }
-
-    pub fn as_base58(&self) -> Result<heapless::String<128>, DigestError> {
-        let mut buffer = heapless::Vec::<u8, 128>::new();
-        bs58::encode(self.0)
-            .onto(buffer.as_mut_slice())
-            .map_err(|_| DigestError::InvalidBase58)?;
-        let str = heapless::String::from_utf8(buffer).map_err(|_| DigestError::InvalidBase58)?;
-        Ok(str)
-    }
-}
-
-impl Serialize for Digest {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        let str = self
-            .as_base58()
-            .map_err(|_| serde::ser::Error::custom("Invalid base58"))?;
-        serializer.serialize_str(&str)
-    }
-}
-
-impl<'de> Deserialize<'de> for Digest {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let hex_str = heapless::String::<128>::deserialize(deserializer)?;
-
-        let mut buffer = [0u8; IOTA_ADDRESS_LENGTH];
-        let length = bs58::decode(hex_str)
-            .onto(&mut buffer)
-            .map_err(|_| serde::de::Error::custom("Invalid hex string"))?;
-
-        if length != IOTA_ADDRESS_LENGTH {
-            return Err(serde::de::Error::custom(
-                "Must be IOTA_ADDRESS_LENGTH bytes",
-            ));
-        }
-
-        Ok(Digest(buffer))
-    }
-}
-
-impl From<Digest> for transaction_types::Digest {
-    fn from(val: Digest) -> Self {
-        transaction_types::Digest::new(val.as_bytes())
-    }
-}
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize)]
-pub struct ExecuteTxRequest {
+pub struct ExecuteTxRequest<T: SignableTransaction, const N: usize = MAX_SERIALIZED_SIGNATURE_SIZE>
+{
     pub reservation_id: u32,
-    pub tx_bytes: BcsData<crate::transaction_types::TransactionData>,
-    pub user_sig: Base64Signature,
+    pub tx_bytes: BcsData<T>,
+    pub user_sig: Base64Signature<N>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -181,27 +43,9 @@ pub struct ReserveGasRequest {
     pub reserve_duration_secs: u32,
 }
 
-#[derive(Debug, Deserialize)]
-pub struct ObjectRef {
-    #[serde(rename = "objectId")]
-    pub object_id: ObjectID,
-    pub version: u32,
-    pub digest: Digest,
-}
-
-impl ObjectRef {
-    pub fn as_tx_object_ref(&self) -> transaction_types::ObjectRef {
-        (
-            self.object_id.clone().into(),
-            transaction_types::SequenceNumber::new(self.version as u64),
-            self.digest.clone().into(),
-        )
-    }
-}
-
 #[derive(Debug, Deserialize)]
 pub struct RequestGasResponse {
-    pub sponsor_address: ObjectID,
+    pub sponsor_address: IotaAddress,
     pub reservation_id: u32,
     pub gas_coins: Vec<ObjectRef, 4>,
 }
@@ -263,11 +107,11 @@ where
         }
     }
 
-    pub async fn execute_tx(
+    pub async fn execute_tx<T: SignableTransaction, const N: usize>(
         &mut self,
         reservation_id: u32,
-        tx_bytes: BcsData<crate::transaction_types::TransactionData>,
-        user_sig: Base64Signature,
+        tx_bytes: BcsData<T>,
+        user_sig: Base64Signature<N>,
     ) -> Result<ExecuteTxResponse, ClientError> {
         let request = ExecuteTxRequest {
             reservation_id,
@@ -288,4 +132,22 @@ where
             Err(e) => Err(e),
         }
     }
+
+    /// Signs `tx` with `signer` and submits it for sponsored execution.
+    pub async fn sign_and_execute_tx<T: SignableTransaction, S: Signer>(
+        &mut self,
+        reservation_id: u32,
+        tx: T,
+        signer: &S,
+    ) -> Result<ExecuteTxResponse, ClientError> {
+        let signature = signer
+            .sign_transaction(&tx)
+            .await
+            .map_err(ClientError::from_signing_error)?;
+        let user_sig: Base64Signature =
+            Base64Signature::new(&signature).map_err(ClientError::from_signing_error)?;
+
+        self.execute_tx(reservation_id, BcsData::new(tx), user_sig)
+            .await
+    }
 }
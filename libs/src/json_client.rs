@@ -12,6 +12,7 @@ pub enum ClientError {
     ParseError(String<512>),
     JsonRpcError(String<512>),
     SerializationError(String<512>),
+    SigningError(String<512>),
 }
 
 impl ClientError {
@@ -27,6 +28,12 @@ impl ClientError {
         ClientError::HttpError(message)
     }
 
+    pub(crate) fn from_signing_error<T: core::fmt::Debug>(error: T) -> Self {
+        let mut message = String::<512>::new();
+        let _ = write!(message, "Signing error: {:?}", error);
+        ClientError::SigningError(message)
+    }
+
     fn from_parse_error<T: core::fmt::Display>(error: T, context: &str) -> Self {
         let mut message = String::<512>::new();
         let _ = write!(message, "{}: {}", context, error);
@@ -4,13 +4,27 @@
 #[cfg(not(feature = "std"))]
 use core::prelude::v1::*;
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 extern crate core;
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod address;
+pub mod base_types;
+pub mod bcs_stream;
 pub mod crypto;
 pub mod encoding;
 pub mod gas_station_client;
 pub mod json_client;
+#[cfg(feature = "alloc")]
+pub mod move_value;
+#[cfg(feature = "alloc")]
+pub mod sensor_track;
+#[cfg(feature = "alloc")]
+pub mod transaction_builder;
+#[cfg(feature = "alloc")]
+pub mod transaction_effects;
+pub mod transaction_ref;
+#[cfg(feature = "alloc")]
 pub mod transaction_types;
@@ -0,0 +1,374 @@
+use crate::address::{IOTA_ADDRESS_LENGTH, IotaAddress};
+use crate::transaction_types::{Event, Identifier, ObjectID, StructTag};
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use serde::de::DeserializeOwned;
+
+/// Describes how the BCS bytes of a Move value are laid out.
+/// Unlike `TypeTag`, struct types carry their field names and types, which the node does not
+/// include in event or object contents.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MoveTypeLayout {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<MoveTypeLayout>),
+    Struct(Box<MoveStructLayout>),
+}
+
+impl MoveTypeLayout {
+    pub fn vector(element: MoveTypeLayout) -> Self {
+        Self::Vector(Box::new(element))
+    }
+
+    /// `0x1::string::String`
+    pub fn string() -> Self {
+        Self::Struct(Box::new(MoveStructLayout::new(
+            framework_tag(1, "string", "String"),
+            alloc::vec![MoveFieldLayout::new(
+                Identifier::from_static("bytes"),
+                Self::vector(Self::U8),
+            )],
+        )))
+    }
+
+    /// `0x2::object::ID`
+    pub fn id() -> Self {
+        Self::Struct(Box::new(MoveStructLayout::new(
+            framework_tag(2, "object", "ID"),
+            alloc::vec![MoveFieldLayout::new(
+                Identifier::from_static("bytes"),
+                Self::Address,
+            )],
+        )))
+    }
+
+    /// `0x2::object::UID`, the first field of every object.
+    pub fn uid() -> Self {
+        Self::Struct(Box::new(MoveStructLayout::new(
+            framework_tag(2, "object", "UID"),
+            alloc::vec![MoveFieldLayout::new(
+                Identifier::from_static("id"),
+                Self::id()
+            )],
+        )))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveStructLayout {
+    pub type_: StructTag,
+    /// In declaration order, which is the BCS order.
+    pub fields: Vec<MoveFieldLayout>,
+}
+
+impl MoveStructLayout {
+    pub fn new(type_: StructTag, fields: Vec<MoveFieldLayout>) -> Self {
+        Self { type_, fields }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveFieldLayout {
+    pub name: Identifier,
+    pub layout: MoveTypeLayout,
+}
+
+impl MoveFieldLayout {
+    pub fn new(name: Identifier, layout: MoveTypeLayout) -> Self {
+        Self { name, layout }
+    }
+}
+
+/// A Move struct without type parameters with a serde-derived Rust counterpart,
+/// so its BCS contents can be decoded directly instead of through `MoveValue`.
+pub trait MoveStructType: DeserializeOwned {
+    const MODULE: Identifier;
+    const NAME: Identifier;
+
+    /// The field layouts, in declaration order.
+    /// `package` is passed on to the layouts of structs from the same package.
+    fn fields(package: ObjectID) -> Vec<MoveFieldLayout>;
+
+    /// `package` is the ID the package was originally published at.
+    fn struct_tag(package: ObjectID) -> StructTag {
+        StructTag {
+            address: package.into(),
+            module: Self::MODULE,
+            name: Self::NAME,
+            type_params: alloc::vec![],
+        }
+    }
+
+    fn layout(package: ObjectID) -> MoveStructLayout {
+        MoveStructLayout::new(Self::struct_tag(package), Self::fields(package))
+    }
+
+    fn from_bcs(bytes: &[u8]) -> Result<Self, bcs::Error> {
+        bcs::from_bytes(bytes)
+    }
+
+    /// Decodes `event` if it has this type, `None` for events of other types.
+    fn from_event(event: &Event, package: ObjectID) -> Option<Result<Self, bcs::Error>> {
+        (event.type_ == Self::struct_tag(package)).then(|| Self::from_bcs(&event.contents))
+    }
+}
+
+fn framework_tag(address: u8, module: &'static str, name: &'static str) -> StructTag {
+    let mut bytes = [0u8; IOTA_ADDRESS_LENGTH];
+    bytes[IOTA_ADDRESS_LENGTH - 1] = address;
+    StructTag {
+        address: IotaAddress::new(bytes),
+        module: Identifier::from_static(module),
+        name: Identifier::from_static(name),
+        type_params: alloc::vec![],
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveValueError {
+    UnexpectedEnd,
+    /// Bytes left over after the value was decoded.
+    TrailingBytes(usize),
+    InvalidBool(u8),
+    InvalidLength,
+}
+
+impl Display for MoveValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            MoveValueError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            MoveValueError::TrailingBytes(count) => write!(f, "{} trailing bytes", count),
+            MoveValueError::InvalidBool(value) => write!(f, "invalid bool {}", value),
+            MoveValueError::InvalidLength => write!(f, "invalid vector length"),
+        }
+    }
+}
+
+/// A decoded Move value.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum MoveValue {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    /// Little endian, as in BCS.
+    U256([u8; 32]),
+    Address(IotaAddress),
+    Signer(IotaAddress),
+    Vector(Vec<MoveValue>),
+    Struct(MoveStruct),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct MoveStruct {
+    pub type_: StructTag,
+    pub fields: Vec<(Identifier, MoveValue)>,
+}
+
+impl MoveStruct {
+    pub fn field(&self, name: &str) -> Option<&MoveValue> {
+        self.fields
+            .iter()
+            .find(|(field, _)| field.as_str() == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl MoveValue {
+    /// Decodes the BCS bytes of a value with the given layout, e.g. event or object contents.
+    pub fn from_bcs(layout: &MoveTypeLayout, bytes: &[u8]) -> Result<Self, MoveValueError> {
+        let mut reader = Reader { bytes };
+        let value = reader.value(layout)?;
+        if !reader.bytes.is_empty() {
+            return Err(MoveValueError::TrailingBytes(reader.bytes.len()));
+        }
+        Ok(value)
+    }
+
+    pub fn as_struct(&self) -> Option<&MoveStruct> {
+        match self {
+            MoveValue::Struct(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The contents of a `vector<u8>`.
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            MoveValue::Vector(values) => values
+                .iter()
+                .map(|value| match value {
+                    MoveValue::U8(byte) => Some(*byte),
+                    _ => None,
+                })
+                .collect(),
+            _ => None,
+        }
+    }
+
+    /// Unwraps the single field structs `0x2::object::UID` and `0x2::object::ID`.
+    pub fn as_object_id(&self) -> Option<IotaAddress> {
+        match self {
+            MoveValue::Address(address) => Some(*address),
+            MoveValue::Struct(value) => match value.fields.as_slice() {
+                [(_, inner)] => inner.as_object_id(),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], MoveValueError> {
+        if self.bytes.len() < len {
+            return Err(MoveValueError::UnexpectedEnd);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], MoveValueError> {
+        Ok(self.take(N)?.try_into().expect("length checked by take"))
+    }
+
+    /// ULEB128, limited to `u32` like BCS.
+    fn length(&mut self) -> Result<usize, MoveValueError> {
+        let mut value: u64 = 0;
+        for shift in (0..32).step_by(7) {
+            let byte = self.array::<1>()?[0];
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return u32::try_from(value)
+                    .map(|value| value as usize)
+                    .map_err(|_| MoveValueError::InvalidLength);
+            }
+        }
+        Err(MoveValueError::InvalidLength)
+    }
+
+    fn value(&mut self, layout: &MoveTypeLayout) -> Result<MoveValue, MoveValueError> {
+        Ok(match layout {
+            MoveTypeLayout::Bool => match self.array::<1>()?[0] {
+                0 => MoveValue::Bool(false),
+                1 => MoveValue::Bool(true),
+                value => return Err(MoveValueError::InvalidBool(value)),
+            },
+            MoveTypeLayout::U8 => MoveValue::U8(self.array::<1>()?[0]),
+            MoveTypeLayout::U16 => MoveValue::U16(u16::from_le_bytes(self.array()?)),
+            MoveTypeLayout::U32 => MoveValue::U32(u32::from_le_bytes(self.array()?)),
+            MoveTypeLayout::U64 => MoveValue::U64(u64::from_le_bytes(self.array()?)),
+            MoveTypeLayout::U128 => MoveValue::U128(u128::from_le_bytes(self.array()?)),
+            MoveTypeLayout::U256 => MoveValue::U256(self.array()?),
+            MoveTypeLayout::Address => MoveValue::Address(IotaAddress::new(self.array()?)),
+            MoveTypeLayout::Signer => MoveValue::Signer(IotaAddress::new(self.array()?)),
+            MoveTypeLayout::Vector(element) => {
+                let len = self.length()?;
+                // Every element takes at least one byte, so this bounds the allocation.
+                if len > self.bytes.len() {
+                    return Err(MoveValueError::UnexpectedEnd);
+                }
+                let mut values = Vec::with_capacity(len);
+                for _ in 0..len {
+                    values.push(self.value(element)?);
+                }
+                MoveValue::Vector(values)
+            }
+            MoveTypeLayout::Struct(layout) => {
+                let mut fields = Vec::with_capacity(layout.fields.len());
+                for field in &layout.fields {
+                    fields.push((field.name.clone(), self.value(&field.layout)?));
+                }
+                MoveValue::Struct(MoveStruct {
+                    type_: layout.type_.clone(),
+                    fields,
+                })
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    #[tokio::test]
+    async fn test_decode_with_layout() {
+        let layout = MoveTypeLayout::Struct(Box::new(MoveStructLayout::new(
+            StructTag::from_str("0x42::example::Thing").unwrap(),
+            alloc::vec![
+                MoveFieldLayout::new(Identifier::from_static("id"), MoveTypeLayout::uid()),
+                MoveFieldLayout::new(Identifier::from_static("name"), MoveTypeLayout::string()),
+                MoveFieldLayout::new(
+                    Identifier::from_static("values"),
+                    MoveTypeLayout::vector(MoveTypeLayout::U16)
+                ),
+                MoveFieldLayout::new(Identifier::from_static("flag"), MoveTypeLayout::Bool),
+                MoveFieldLayout::new(Identifier::from_static("big"), MoveTypeLayout::U128),
+            ],
+        )));
+
+        let id = IotaAddress::new([7; 32]);
+        let bytes = bcs::to_bytes(&(id, "ISS", &[1u16, 515][..], true, 1u128 << 100)).unwrap();
+
+        let value = MoveValue::from_bcs(&layout, &bytes).unwrap();
+        let value = value.as_struct().unwrap();
+        assert_eq!(value.type_.to_string(), "0x42::example::Thing");
+        assert_eq!(value.field("id").unwrap().as_object_id(), Some(id));
+        let name = value.field("name").unwrap().as_struct().unwrap();
+        assert_eq!(name.type_.to_string(), "0x1::string::String");
+        assert_eq!(
+            name.field("bytes").unwrap().as_bytes(),
+            Some(b"ISS".to_vec())
+        );
+        assert_eq!(
+            value.field("values"),
+            Some(&MoveValue::Vector(alloc::vec![
+                MoveValue::U16(1),
+                MoveValue::U16(515)
+            ]))
+        );
+        assert_eq!(value.field("flag"), Some(&MoveValue::Bool(true)));
+        assert_eq!(value.field("big"), Some(&MoveValue::U128(1 << 100)));
+        assert_eq!(value.field("missing"), None);
+
+        assert_eq!(
+            MoveValue::from_bcs(&layout, &bytes[..bytes.len() - 1]),
+            Err(MoveValueError::UnexpectedEnd)
+        );
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert_eq!(
+            MoveValue::from_bcs(&layout, &trailing),
+            Err(MoveValueError::TrailingBytes(1))
+        );
+        assert_eq!(
+            MoveValue::from_bcs(&MoveTypeLayout::Bool, &[2]),
+            Err(MoveValueError::InvalidBool(2))
+        );
+        // A vector claiming more elements than there are bytes.
+        assert_eq!(
+            MoveValue::from_bcs(
+                &MoveTypeLayout::vector(MoveTypeLayout::U8),
+                &[0xff, 0x01, 0]
+            ),
+            Err(MoveValueError::UnexpectedEnd)
+        );
+    }
+}
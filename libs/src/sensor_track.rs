@@ -0,0 +1,151 @@
+use crate::move_value::{MoveFieldLayout, MoveStructType, MoveTypeLayout};
+use crate::transaction_types::{Identifier, ObjectID};
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// `sensor_track::main::SensorMetadata`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SensorMetadata {
+    pub sensor_id: u8,
+    pub location: String,
+    pub battery_level: u8,
+}
+
+impl MoveStructType for SensorMetadata {
+    const MODULE: Identifier = Identifier::from_static("main");
+    const NAME: Identifier = Identifier::from_static("SensorMetadata");
+
+    fn fields(_package: ObjectID) -> Vec<MoveFieldLayout> {
+        alloc::vec![
+            MoveFieldLayout::new(Identifier::from_static("sensor_id"), MoveTypeLayout::U8),
+            MoveFieldLayout::new(
+                Identifier::from_static("location"),
+                MoveTypeLayout::string()
+            ),
+            MoveFieldLayout::new(Identifier::from_static("battery_level"), MoveTypeLayout::U8),
+        ]
+    }
+}
+
+/// `sensor_track::temperature::TemperatureReading`, the object created for every reading.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemperatureReading {
+    /// The `UID`, which is encoded like a plain object ID.
+    pub id: ObjectID,
+    pub metadata: SensorMetadata,
+    pub temperature_celsius: u32,
+}
+
+impl MoveStructType for TemperatureReading {
+    const MODULE: Identifier = Identifier::from_static("temperature");
+    const NAME: Identifier = Identifier::from_static("TemperatureReading");
+
+    fn fields(package: ObjectID) -> Vec<MoveFieldLayout> {
+        alloc::vec![
+            MoveFieldLayout::new(Identifier::from_static("id"), MoveTypeLayout::uid()),
+            MoveFieldLayout::new(
+                Identifier::from_static("metadata"),
+                MoveTypeLayout::Struct(alloc::boxed::Box::new(SensorMetadata::layout(package))),
+            ),
+            MoveFieldLayout::new(
+                Identifier::from_static("temperature_celsius"),
+                MoveTypeLayout::U32,
+            ),
+        ]
+    }
+}
+
+/// `sensor_track::temperature::TemperatureRecorded`, emitted by `push_reading`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemperatureRecorded {
+    pub sensor_id: u8,
+    /// The ID of the created `TemperatureReading`.
+    pub reading_id: ObjectID,
+    pub temperature_celsius: u32,
+}
+
+impl MoveStructType for TemperatureRecorded {
+    const MODULE: Identifier = Identifier::from_static("temperature");
+    const NAME: Identifier = Identifier::from_static("TemperatureRecorded");
+
+    fn fields(_package: ObjectID) -> Vec<MoveFieldLayout> {
+        alloc::vec![
+            MoveFieldLayout::new(Identifier::from_static("sensor_id"), MoveTypeLayout::U8),
+            MoveFieldLayout::new(Identifier::from_static("reading_id"), MoveTypeLayout::id()),
+            MoveFieldLayout::new(
+                Identifier::from_static("temperature_celsius"),
+                MoveTypeLayout::U32,
+            ),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::IotaAddress;
+    use crate::move_value::{MoveTypeLayout, MoveValue};
+    use crate::transaction_types::Event;
+    use core::str::FromStr;
+
+    #[tokio::test]
+    async fn test_decode_reading_and_event() {
+        let package = ObjectID::from_hex("0x42").unwrap();
+        let reading = TemperatureReading {
+            id: ObjectID::new([7; 32]),
+            metadata: SensorMetadata {
+                sensor_id: 123,
+                location: "ISS".into(),
+                battery_level: 255,
+            },
+            temperature_celsius: 12345,
+        };
+        let contents = bcs::to_bytes(&reading).unwrap();
+        assert_eq!(TemperatureReading::from_bcs(&contents).unwrap(), reading);
+
+        // The generic decoder reads the same bytes.
+        let layout =
+            MoveTypeLayout::Struct(alloc::boxed::Box::new(TemperatureReading::layout(package)));
+        let value = MoveValue::from_bcs(&layout, &contents).unwrap();
+        let value = value.as_struct().unwrap();
+        assert_eq!(
+            value.type_.to_string(),
+            "0x42::temperature::TemperatureReading"
+        );
+        assert_eq!(
+            value.field("id").unwrap().as_object_id(),
+            Some(reading.id.into())
+        );
+        let metadata = value.field("metadata").unwrap().as_struct().unwrap();
+        assert_eq!(metadata.type_.to_string(), "0x42::main::SensorMetadata");
+        assert_eq!(metadata.field("sensor_id"), Some(&MoveValue::U8(123)));
+        assert_eq!(
+            value.field("temperature_celsius"),
+            Some(&MoveValue::U32(12345))
+        );
+
+        let recorded = TemperatureRecorded {
+            sensor_id: 123,
+            reading_id: reading.id,
+            temperature_celsius: 12345,
+        };
+        let event = Event {
+            package_id: package,
+            transaction_module: Identifier::from_static("temperature"),
+            sender: IotaAddress::from_str("0xa11ce").unwrap(),
+            type_: TemperatureRecorded::struct_tag(package),
+            contents: bcs::to_bytes(&recorded).unwrap(),
+        };
+        assert_eq!(
+            TemperatureRecorded::from_event(&event, package)
+                .unwrap()
+                .unwrap(),
+            recorded
+        );
+        // Same struct name from another package.
+        assert!(
+            TemperatureRecorded::from_event(&event, ObjectID::from_hex("0x43").unwrap()).is_none()
+        );
+    }
+}
@@ -0,0 +1,426 @@
+use crate::address::IotaAddress;
+pub use crate::base_types::BuilderError;
+use crate::base_types::check_gas;
+use crate::bcs_stream::{serialize_into, serialized_size};
+use crate::transaction_types::{
+    Argument, CallArg, Command, EpochId, GasData, Identifier, ObjectArg, ObjectID, ObjectRef,
+    ProgrammableMoveCall, ProgrammableTransaction, TransactionData, TransactionDataV1,
+    TransactionExpiration, TransactionKind, TypeTag,
+};
+use alloc::vec::Vec;
+use serde::Serialize;
+
+/// Builds a `ProgrammableTransaction` without tracking input and result indices by hand.
+///
+/// Every method returns the `Argument` referring to what it added, to be passed on to later
+/// commands. Identical pure values and repeated objects share one input.
+#[derive(Debug, Default)]
+pub struct ProgrammableTransactionBuilder {
+    inputs: Vec<CallArg>,
+    commands: Vec<Command>,
+}
+
+impl ProgrammableTransactionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the BCS encoding of `value` as a pure input.
+    /// The input is allocated once at its exact size, to keep small heaps from fragmenting.
+    pub fn pure<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<Argument, BuilderError> {
+        let mut bytes = Vec::with_capacity(serialized_size(value)?);
+        serialize_into(&mut bytes, value)?;
+        self.input(CallArg::Pure(bytes))
+    }
+
+    pub fn obj(&mut self, obj_arg: ObjectArg) -> Result<Argument, BuilderError> {
+        self.input(CallArg::Object(obj_arg))
+    }
+
+    /// Adds an input, reusing an existing one for the same pure bytes or the same object.
+    /// A shared object passed both mutably and immutably becomes a single mutable input.
+    pub fn input(&mut self, call_arg: CallArg) -> Result<Argument, BuilderError> {
+        let existing = self
+            .inputs
+            .iter()
+            .position(|input| match (input, &call_arg) {
+                (CallArg::Pure(a), CallArg::Pure(b)) => a == b,
+                (CallArg::Object(a), CallArg::Object(b)) => a.id() == b.id(),
+                _ => false,
+            });
+
+        let index = match existing {
+            Some(index) => {
+                if let (CallArg::Object(existing), CallArg::Object(new)) =
+                    (&mut self.inputs[index], &call_arg)
+                {
+                    merge_object_args(existing, new)?;
+                }
+                index
+            }
+            None => {
+                // Checked before pushing, so a rejected input is not part of the transaction.
+                u16::try_from(self.inputs.len()).map_err(|_| BuilderError::TooManyInputs)?;
+                self.inputs.push(call_arg);
+                self.inputs.len() - 1
+            }
+        };
+
+        u16::try_from(index)
+            .map(Argument::Input)
+            .map_err(|_| BuilderError::TooManyInputs)
+    }
+
+    /// Appends `command` and returns its `Argument::Result`.
+    /// Commands returning several values are accessed with `Argument::NestedResult`.
+    pub fn command(&mut self, command: Command) -> Result<Argument, BuilderError> {
+        let index =
+            u16::try_from(self.commands.len()).map_err(|_| BuilderError::TooManyCommands)?;
+        self.commands.push(command);
+        Ok(Argument::Result(index))
+    }
+
+    pub fn move_call(
+        &mut self,
+        package: ObjectID,
+        module: Identifier,
+        function: Identifier,
+        type_arguments: Vec<TypeTag>,
+        arguments: Vec<Argument>,
+    ) -> Result<Argument, BuilderError> {
+        self.command(Command::MoveCall(alloc::boxed::Box::new(
+            ProgrammableMoveCall {
+                package,
+                module,
+                function,
+                type_arguments,
+                arguments,
+            },
+        )))
+    }
+
+    pub fn transfer_objects(
+        &mut self,
+        objects: Vec<Argument>,
+        recipient: Argument,
+    ) -> Result<Argument, BuilderError> {
+        self.command(Command::TransferObjects(objects, recipient))
+    }
+
+    /// The new coins are `Argument::NestedResult(i, n)` for the n-th amount.
+    pub fn split_coins(
+        &mut self,
+        coin: Argument,
+        amounts: Vec<Argument>,
+    ) -> Result<Argument, BuilderError> {
+        self.command(Command::SplitCoins(coin, amounts))
+    }
+
+    pub fn merge_coins(
+        &mut self,
+        target: Argument,
+        coins: Vec<Argument>,
+    ) -> Result<Argument, BuilderError> {
+        self.command(Command::MergeCoins(target, coins))
+    }
+
+    pub fn make_move_vec(
+        &mut self,
+        type_tag: Option<TypeTag>,
+        elements: Vec<Argument>,
+    ) -> Result<Argument, BuilderError> {
+        self.command(Command::MakeMoveVec(type_tag, elements))
+    }
+
+    pub fn finish(self) -> ProgrammableTransaction {
+        ProgrammableTransaction {
+            inputs: self.inputs,
+            commands: self.commands,
+        }
+    }
+}
+
+fn merge_object_args(existing: &mut ObjectArg, new: &ObjectArg) -> Result<(), BuilderError> {
+    match (existing, new) {
+        (
+            ObjectArg::SharedObject {
+                initial_shared_version,
+                mutable,
+                ..
+            },
+            ObjectArg::SharedObject {
+                initial_shared_version: new_version,
+                mutable: new_mutable,
+                ..
+            },
+        ) if initial_shared_version == new_version => {
+            *mutable |= *new_mutable;
+            Ok(())
+        }
+        (existing, new) if existing == new => Ok(()),
+        (_, new) => Err(BuilderError::ConflictingObjectInput(new.id())),
+    }
+}
+
+/// Assembles `TransactionData` around a built `ProgrammableTransaction`.
+///
+/// The gas owner is the sponsor if one is set, otherwise the sender.
+/// `build` checks the gas invariants the node would otherwise reject the transaction for.
+#[derive(Debug, Clone)]
+pub struct TransactionDataBuilder {
+    pt: ProgrammableTransaction,
+    sender: IotaAddress,
+    sponsor: Option<IotaAddress>,
+    payment: Vec<ObjectRef>,
+    budget: Option<u64>,
+    price: Option<u64>,
+    expiration: TransactionExpiration,
+}
+
+impl TransactionDataBuilder {
+    pub fn new(pt: ProgrammableTransaction, sender: IotaAddress) -> Self {
+        Self {
+            pt,
+            sender,
+            sponsor: None,
+            payment: Vec::new(),
+            budget: None,
+            price: None,
+            expiration: TransactionExpiration::None,
+        }
+    }
+
+    pub fn sponsor(mut self, sponsor: IotaAddress) -> Self {
+        self.sponsor = Some(sponsor);
+        self
+    }
+
+    pub fn gas_payment(mut self, coins: Vec<ObjectRef>) -> Self {
+        self.payment = coins;
+        self
+    }
+
+    pub fn gas_budget(mut self, budget: u64) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    pub fn gas_price(mut self, price: u64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    /// The transaction is rejected once `epoch` has ended.
+    pub fn expiration(mut self, epoch: EpochId) -> Self {
+        self.expiration = TransactionExpiration::Epoch(epoch);
+        self
+    }
+
+    pub fn build(self) -> Result<TransactionData, BuilderError> {
+        let (price, budget) = check_gas(&self.payment, self.price, self.budget)?;
+
+        Ok(TransactionData::V1(TransactionDataV1 {
+            kind: TransactionKind::ProgrammableTransaction1(self.pt),
+            sender: self.sender,
+            gas_data: GasData {
+                payment: self.payment,
+                owner: self.sponsor.unwrap_or(self.sender),
+                price,
+                budget,
+            },
+            expiration: self.expiration,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction_types::{Digest, ObjectRef, SequenceNumber};
+    use core::str::FromStr;
+
+    #[tokio::test]
+    async fn test_build_move_call() {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+
+        let sensor_id = ptb.pure(&123u8).unwrap();
+        let location = ptb.pure("ISS").unwrap();
+        let battery = ptb.pure(&255u8).unwrap();
+        // Same bytes as `battery`.
+        let battery_again = ptb.pure(&255u8).unwrap();
+        let temperature = ptb.pure(&12345u32).unwrap();
+        assert_eq!(battery, battery_again);
+
+        let result = ptb
+            .move_call(
+                ObjectID::from_hex("0x42").unwrap(),
+                Identifier::from_static("temperature"),
+                Identifier::from_static("push_reading"),
+                alloc::vec![],
+                alloc::vec![sensor_id, location, battery, temperature],
+            )
+            .unwrap();
+        assert_eq!(result, Argument::Result(0));
+
+        let pt = ptb.finish();
+        assert_eq!(
+            pt.inputs,
+            alloc::vec![
+                CallArg::Pure(alloc::vec![123]),
+                CallArg::Pure(alloc::vec![3, b'I', b'S', b'S']),
+                CallArg::Pure(alloc::vec![255]),
+                CallArg::Pure(12345u32.to_le_bytes().to_vec()),
+            ]
+        );
+        assert_eq!(
+            pt.commands,
+            alloc::vec![Command::MoveCall(alloc::boxed::Box::new(
+                ProgrammableMoveCall {
+                    package: ObjectID::from_hex("0x42").unwrap(),
+                    module: Identifier::from_static("temperature"),
+                    function: Identifier::from_static("push_reading"),
+                    type_arguments: alloc::vec![],
+                    arguments: alloc::vec![
+                        Argument::Input(0),
+                        Argument::Input(1),
+                        Argument::Input(2),
+                        Argument::Input(3)
+                    ],
+                }
+            ))]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_coin_commands() {
+        let coin = ObjectArg::ImmOrOwnedObject(ObjectRef::new(
+            ObjectID::from_hex("0x5").unwrap(),
+            SequenceNumber::new(3),
+            Digest::new([9; 32]),
+        ));
+        let recipient = IotaAddress::from_str("0xa11ce").unwrap();
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        let amount = ptb.pure(&1000u64).unwrap();
+        let split = ptb
+            .split_coins(Argument::GasCoin, alloc::vec![amount, amount])
+            .unwrap();
+        let first = Argument::NestedResult(0, 0);
+        let second = Argument::NestedResult(0, 1);
+        assert_eq!(split, Argument::Result(0));
+
+        let coin = ptb.obj(coin).unwrap();
+        ptb.merge_coins(coin, alloc::vec![second]).unwrap();
+        let coins = ptb.make_move_vec(None, alloc::vec![first, coin]).unwrap();
+        let recipient = ptb.pure(&recipient).unwrap();
+        assert_eq!(
+            ptb.transfer_objects(alloc::vec![coins], recipient).unwrap(),
+            Argument::Result(3)
+        );
+
+        let pt = ptb.finish();
+        assert_eq!(pt.inputs.len(), 3);
+        assert_eq!(
+            pt.commands[2],
+            Command::MakeMoveVec(None, alloc::vec![first, Argument::Input(1)])
+        );
+        assert_eq!(
+            pt.commands[3],
+            Command::TransferObjects(alloc::vec![Argument::Result(2)], Argument::Input(2))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_object_dedup() {
+        let id = ObjectID::from_hex("0x6").unwrap();
+        let shared = |mutable| ObjectArg::SharedObject {
+            id,
+            initial_shared_version: SequenceNumber::new(1),
+            mutable,
+        };
+
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        let clock = ptb.obj(shared(false)).unwrap();
+        assert_eq!(ptb.obj(shared(true)).unwrap(), clock);
+        assert_eq!(ptb.obj(shared(false)).unwrap(), clock);
+
+        let owned =
+            ObjectArg::ImmOrOwnedObject(ObjectRef::new(id, SequenceNumber::new(1), Digest::ZERO));
+        assert_eq!(
+            ptb.obj(owned),
+            Err(BuilderError::ConflictingObjectInput(id))
+        );
+
+        let pt = ptb.finish();
+        assert_eq!(pt.inputs, alloc::vec![CallArg::Object(shared(true))]);
+    }
+
+    #[tokio::test]
+    async fn test_too_many_inputs_not_added() {
+        let mut ptb = ProgrammableTransactionBuilder::new();
+        ptb.inputs = (0..=u32::from(u16::MAX))
+            .map(|i| CallArg::Pure(i.to_le_bytes().to_vec()))
+            .collect();
+
+        assert_eq!(ptb.pure(&u64::MAX), Err(BuilderError::TooManyInputs));
+        assert_eq!(ptb.finish().inputs.len(), usize::from(u16::MAX) + 1);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_data() {
+        let sender = IotaAddress::from_str("0x5e4d").unwrap();
+        let sponsor = IotaAddress::from_str("0x5b05").unwrap();
+        let coin = |id| {
+            ObjectRef::new(
+                ObjectID::new([id; 32]),
+                SequenceNumber::new(7),
+                Digest::ZERO,
+            )
+        };
+        let builder = |payment| {
+            TransactionDataBuilder::new(ProgrammableTransactionBuilder::new().finish(), sender)
+                .gas_payment(payment)
+                .gas_budget(1_000_000)
+                .gas_price(1000)
+        };
+
+        let TransactionData::V1(data) = builder(alloc::vec![coin(1), coin(2)])
+            .sponsor(sponsor)
+            .expiration(12)
+            .build()
+            .unwrap();
+        assert_eq!(data.sender, sender);
+        assert_eq!(data.gas_data.owner, sponsor);
+        assert_eq!(data.gas_data.price, 1000);
+        assert_eq!(data.gas_data.budget, 1_000_000);
+        assert_eq!(data.gas_data.payment, alloc::vec![coin(1), coin(2)]);
+        assert_eq!(data.expiration, TransactionExpiration::Epoch(12));
+
+        let TransactionData::V1(data) = builder(alloc::vec![coin(1)]).build().unwrap();
+        assert_eq!(data.gas_data.owner, sender);
+        assert_eq!(data.expiration, TransactionExpiration::None);
+
+        assert_eq!(
+            builder(alloc::vec![]).build(),
+            Err(BuilderError::NoGasPayment)
+        );
+        assert_eq!(
+            builder(alloc::vec![coin(1), coin(2), coin(1)]).build(),
+            Err(BuilderError::DuplicateGasPayment(ObjectID::new([1; 32])))
+        );
+        assert_eq!(
+            builder(alloc::vec![coin(1)]).gas_budget(999).build(),
+            Err(BuilderError::GasBudgetTooLow {
+                budget: 999,
+                price: 1000
+            })
+        );
+        assert_eq!(
+            TransactionDataBuilder::new(ProgrammableTransactionBuilder::new().finish(), sender)
+                .gas_payment(alloc::vec![coin(1)])
+                .gas_budget(1000)
+                .build(),
+            Err(BuilderError::MissingGasPrice)
+        );
+    }
+}
@@ -0,0 +1,533 @@
+use crate::address::IotaAddress;
+use crate::transaction_types::{
+    Digest, EpochId, Identifier, ObjectID, ObjectRef, Owner, SequenceNumber,
+};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+/// The outcome of executing a transaction, as returned in raw BCS by the node.
+/// The variant order is the BCS enum tag, do not reorder.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum TransactionEffects {
+    V1(TransactionEffectsV1),
+}
+
+impl TransactionEffects {
+    pub fn from_bcs(bytes: &[u8]) -> Result<Self, bcs::Error> {
+        bcs::from_bytes(bytes)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct TransactionEffectsV1 {
+    pub status: ExecutionStatus,
+    /// The epoch the transaction was executed in.
+    pub epoch: EpochId,
+    pub gas_used: GasCostSummary,
+    pub transaction_digest: Digest,
+    /// Index of the gas object in `changed_objects`, `None` for system transactions.
+    pub gas_object_index: Option<u32>,
+    pub events_digest: Option<Digest>,
+    /// Digests of the transactions that created the inputs of this one.
+    pub dependencies: Vec<Digest>,
+    /// The version every written object has after the transaction.
+    pub lamport_version: SequenceNumber,
+    pub changed_objects: Vec<ChangedObject>,
+    pub unchanged_shared_objects: Vec<UnchangedSharedObject>,
+    pub auxiliary_data_digest: Option<Digest>,
+}
+
+impl TransactionEffectsV1 {
+    /// Objects and packages created by the transaction, with their new references.
+    pub fn created(&self) -> Vec<(ObjectRef, Owner)> {
+        self.written(|object| object.id_operation == IdOperation::Created)
+    }
+
+    /// Objects that existed before the transaction and were written by it.
+    pub fn mutated(&self) -> Vec<(ObjectRef, Owner)> {
+        self.written(|object| {
+            object.id_operation == IdOperation::None
+                && matches!(object.input_state, ObjectIn::Exist { .. })
+        })
+    }
+
+    /// Objects deleted by the transaction, referenced at `lamport_version`.
+    pub fn deleted(&self) -> Vec<ObjectRef> {
+        self.changed_objects
+            .iter()
+            .filter(|object| {
+                object.id_operation == IdOperation::Deleted
+                    && object.output_state == ObjectOut::NotExist
+            })
+            .map(|object| {
+                ObjectRef::new(
+                    object.object_id,
+                    self.lamport_version,
+                    Digest::OBJECT_DELETED,
+                )
+            })
+            .collect()
+    }
+
+    /// The gas coin after paying for the transaction.
+    pub fn gas_object(&self) -> Option<(ObjectRef, Owner)> {
+        let object = self.changed_objects.get(self.gas_object_index? as usize)?;
+        self.written_ref(object)
+    }
+
+    fn written(&self, filter: impl Fn(&ChangedObject) -> bool) -> Vec<(ObjectRef, Owner)> {
+        self.changed_objects
+            .iter()
+            .filter(|object| filter(object))
+            .filter_map(|object| self.written_ref(object))
+            .collect()
+    }
+
+    fn written_ref(&self, object: &ChangedObject) -> Option<(ObjectRef, Owner)> {
+        match object.output_state {
+            ObjectOut::NotExist => None,
+            ObjectOut::ObjectWrite { digest, owner } => Some((
+                ObjectRef::new(object.object_id, self.lamport_version, digest),
+                owner,
+            )),
+            ObjectOut::PackageWrite { version, digest } => Some((
+                ObjectRef::new(object.object_id, version, digest),
+                Owner::Immutable,
+            )),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct GasCostSummary {
+    pub computation_cost: u64,
+    /// The part of `computation_cost` that is burned instead of paid to validators.
+    pub computation_cost_burned: u64,
+    /// Storage cost of all objects created or mutated.
+    pub storage_cost: u64,
+    /// Refund for all objects deleted or mutated.
+    pub storage_rebate: u64,
+    /// The part of the rebate that is kept by the system.
+    pub non_refundable_storage_fee: u64,
+}
+
+impl GasCostSummary {
+    /// What the gas owner was charged before the rebate.
+    /// Saturates, the fields come from decoded bytes and are not trusted to add up.
+    pub fn gas_used(&self) -> u64 {
+        self.computation_cost.saturating_add(self.storage_cost)
+    }
+
+    /// What the gas owner paid in total, negative if the rebate exceeded the costs.
+    /// Computed in `i128`, which holds any combination of the `u64` fields exactly.
+    pub fn net_gas_usage(&self) -> i128 {
+        i128::from(self.computation_cost) + i128::from(self.storage_cost)
+            - i128::from(self.storage_rebate)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct ChangedObject {
+    pub object_id: ObjectID,
+    pub input_state: ObjectIn,
+    pub output_state: ObjectOut,
+    pub id_operation: IdOperation,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ObjectIn {
+    NotExist,
+    Exist {
+        version: SequenceNumber,
+        digest: Digest,
+        owner: Owner,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ObjectOut {
+    NotExist,
+    /// Written at the transaction's `lamport_version`.
+    ObjectWrite {
+        digest: Digest,
+        owner: Owner,
+    },
+    PackageWrite {
+        version: SequenceNumber,
+        digest: Digest,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum IdOperation {
+    None,
+    Created,
+    Deleted,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub struct UnchangedSharedObject {
+    pub object_id: ObjectID,
+    pub kind: UnchangedSharedKind,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum UnchangedSharedKind {
+    ReadOnlyRoot {
+        version: SequenceNumber,
+        digest: Digest,
+    },
+    MutateDeleted {
+        version: SequenceNumber,
+    },
+    ReadDeleted {
+        version: SequenceNumber,
+    },
+    Cancelled {
+        version: SequenceNumber,
+    },
+    PerEpochConfig,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum ExecutionStatus {
+    Success,
+    Failure {
+        error: ExecutionFailureStatus,
+        /// Index of the command that failed, if the failure is attributable to one.
+        command: Option<u64>,
+    },
+}
+
+impl ExecutionStatus {
+    pub fn is_success(&self) -> bool {
+        matches!(self, ExecutionStatus::Success)
+    }
+}
+
+/// Why a transaction failed. The variant order is the BCS enum tag, do not reorder.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub enum ExecutionFailureStatus {
+    InsufficientGas,
+    InvalidGasObject,
+    InvariantViolation,
+    FeatureNotYetSupported,
+    MoveObjectTooBig {
+        object_size: u64,
+        max_object_size: u64,
+    },
+    MovePackageTooBig {
+        object_size: u64,
+        max_object_size: u64,
+    },
+    CircularObjectOwnership {
+        object: ObjectID,
+    },
+    InsufficientCoinBalance,
+    CoinBalanceOverflow,
+    PublishErrorNonZeroAddress,
+    IotaMoveVerificationError,
+    MovePrimitiveRuntimeError(Option<MoveLocation>),
+    /// The abort location and code of a failed `assert!` or `abort`.
+    MoveAbort(MoveLocation, u64),
+    VMVerificationOrDeserializationError,
+    VMInvariantViolation,
+    FunctionNotFound,
+    ArityMismatch,
+    TypeArityMismatch,
+    NonEntryFunctionInvoked,
+    CommandArgumentError {
+        arg_idx: u16,
+        kind: CommandArgumentError,
+    },
+    TypeArgumentError {
+        argument_idx: u16,
+        kind: TypeArgumentError,
+    },
+    UnusedValueWithoutDrop {
+        result_idx: u16,
+        secondary_idx: u16,
+    },
+    InvalidPublicFunctionReturnType {
+        idx: u16,
+    },
+    InvalidTransferObject,
+    EffectsTooLarge {
+        current_size: u64,
+        max_size: u64,
+    },
+    PublishUpgradeMissingDependency,
+    PublishUpgradeDependencyDowngrade,
+    PackageUpgradeError {
+        upgrade_error: PackageUpgradeError,
+    },
+    WrittenObjectsTooLarge {
+        current_size: u64,
+        max_size: u64,
+    },
+    CertificateDenied,
+    IotaMoveVerificationTimedout,
+    SharedObjectOperationNotAllowed,
+    InputObjectDeleted,
+    ExecutionCancelledDueToSharedObjectCongestion {
+        congested_objects: Vec<ObjectID>,
+    },
+    AddressDeniedForCoin {
+        address: IotaAddress,
+        coin_type: alloc::string::String,
+    },
+    CoinTypeGlobalPause {
+        coin_type: alloc::string::String,
+    },
+    ExecutionCancelledDueToRandomnessUnavailable,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct MoveLocation {
+    pub package: ObjectID,
+    pub module: Identifier,
+    pub function: u16,
+    /// Bytecode offset within the function.
+    pub instruction: u16,
+    pub function_name: Option<Identifier>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum CommandArgumentError {
+    TypeMismatch,
+    InvalidBCSBytes,
+    InvalidUsageOfPureArg,
+    InvalidArgumentToPrivateEntryFunction,
+    IndexOutOfBounds { idx: u16 },
+    SecondaryIndexOutOfBounds { result_idx: u16, secondary_idx: u16 },
+    InvalidResultArity { result_idx: u16 },
+    InvalidGasCoinUsage,
+    InvalidValueUsage,
+    InvalidObjectByValue,
+    InvalidObjectByMutRef,
+    SharedObjectOperationNotAllowed,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum TypeArgumentError {
+    TypeNotFound,
+    ConstraintNotSatisfied,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum PackageUpgradeError {
+    UnableToFetchPackage {
+        package_id: ObjectID,
+    },
+    NotAPackage {
+        object_id: ObjectID,
+    },
+    IncompatibleUpgrade,
+    DigestDoesNotMatch {
+        digest: Digest,
+    },
+    UnknownUpgradePolicy {
+        policy: u8,
+    },
+    PackageIdDoesNotMatch {
+        package_id: ObjectID,
+        ticket_id: ObjectID,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::str::FromStr;
+
+    fn push_u64(bytes: &mut Vec<u8>, value: u64) {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn push_digest(bytes: &mut Vec<u8>, fill: u8) {
+        bytes.push(32);
+        bytes.extend_from_slice(&[fill; 32]);
+    }
+
+    #[tokio::test]
+    async fn test_decode_effects() {
+        let sender = IotaAddress::from_str("0xa11ce").unwrap();
+        let package = ObjectID::from_hex("0x42").unwrap();
+
+        // Built field by field to check the layout independently of the serde derives.
+        let mut bytes = alloc::vec![0, 0]; // V1, Success
+        push_u64(&mut bytes, 12); // epoch
+        for cost in [1_000_000, 400_000, 2_000_000, 980_000, 20_000] {
+            push_u64(&mut bytes, cost);
+        }
+        push_digest(&mut bytes, 1); // transaction_digest
+        bytes.extend_from_slice(&[1, 0, 0, 0, 0]); // gas_object_index: Some(0)
+        bytes.push(1);
+        push_digest(&mut bytes, 2); // events_digest
+        bytes.push(1);
+        push_digest(&mut bytes, 3); // dependencies
+        push_u64(&mut bytes, 40); // lamport_version
+
+        bytes.push(3); // changed_objects
+        // Gas coin: mutated, address owned.
+        bytes.extend_from_slice(&[5; 32]);
+        bytes.push(1);
+        push_u64(&mut bytes, 39);
+        push_digest(&mut bytes, 4);
+        bytes.push(0);
+        bytes.extend_from_slice(sender.as_bytes());
+        bytes.push(1);
+        push_digest(&mut bytes, 5);
+        bytes.push(0);
+        bytes.extend_from_slice(sender.as_bytes());
+        bytes.push(0);
+        // Created shared object.
+        bytes.extend_from_slice(&[6; 32]);
+        bytes.push(0);
+        bytes.push(1);
+        push_digest(&mut bytes, 6);
+        bytes.push(2);
+        push_u64(&mut bytes, 40);
+        bytes.push(1);
+        // Deleted object.
+        bytes.extend_from_slice(&[7; 32]);
+        bytes.push(1);
+        push_u64(&mut bytes, 2);
+        push_digest(&mut bytes, 7);
+        bytes.push(3);
+        bytes.push(0);
+        bytes.push(2);
+
+        bytes.push(1); // unchanged_shared_objects
+        bytes.extend_from_slice(package.as_bytes());
+        bytes.push(0);
+        push_u64(&mut bytes, 1);
+        push_digest(&mut bytes, 8);
+        bytes.push(0); // auxiliary_data_digest: None
+
+        let TransactionEffects::V1(effects) = TransactionEffects::from_bcs(&bytes).unwrap();
+        assert!(effects.status.is_success());
+        assert_eq!(effects.epoch, 12);
+        assert_eq!(
+            effects.gas_used,
+            GasCostSummary {
+                computation_cost: 1_000_000,
+                computation_cost_burned: 400_000,
+                storage_cost: 2_000_000,
+                storage_rebate: 980_000,
+                non_refundable_storage_fee: 20_000,
+            }
+        );
+        assert_eq!(effects.gas_used.net_gas_usage(), 2_020_000);
+        assert_eq!(effects.events_digest, Some(Digest::new([2; 32])));
+        assert_eq!(
+            effects.unchanged_shared_objects,
+            alloc::vec![UnchangedSharedObject {
+                object_id: package,
+                kind: UnchangedSharedKind::ReadOnlyRoot {
+                    version: SequenceNumber::new(1),
+                    digest: Digest::new([8; 32]),
+                },
+            }]
+        );
+
+        let gas = (
+            ObjectRef::new(
+                ObjectID::new([5; 32]),
+                SequenceNumber::new(40),
+                Digest::new([5; 32]),
+            ),
+            Owner::AddressOwner(sender),
+        );
+        assert_eq!(effects.gas_object(), Some(gas));
+        assert_eq!(effects.mutated(), alloc::vec![gas]);
+        assert_eq!(
+            effects.created(),
+            alloc::vec![(
+                ObjectRef::new(
+                    ObjectID::new([6; 32]),
+                    SequenceNumber::new(40),
+                    Digest::new([6; 32]),
+                ),
+                Owner::Shared {
+                    initial_shared_version: SequenceNumber::new(40)
+                },
+            )]
+        );
+        assert_eq!(
+            effects.deleted(),
+            alloc::vec![ObjectRef::new(
+                ObjectID::new([7; 32]),
+                SequenceNumber::new(40),
+                Digest::OBJECT_DELETED,
+            )]
+        );
+
+        assert_eq!(
+            bcs::to_bytes(&TransactionEffects::V1(effects)).unwrap(),
+            bytes
+        );
+    }
+
+    #[tokio::test]
+    async fn test_gas_summary_does_not_overflow() {
+        let summary = GasCostSummary {
+            computation_cost: u64::MAX,
+            computation_cost_burned: 0,
+            storage_cost: u64::MAX,
+            storage_rebate: 0,
+            non_refundable_storage_fee: 0,
+        };
+        assert_eq!(summary.gas_used(), u64::MAX);
+        assert_eq!(summary.net_gas_usage(), 2 * i128::from(u64::MAX));
+
+        let refund = GasCostSummary {
+            computation_cost: 0,
+            storage_cost: 0,
+            storage_rebate: u64::MAX,
+            ..summary
+        };
+        assert_eq!(refund.net_gas_usage(), -i128::from(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_decode_move_abort() {
+        let status = ExecutionStatus::Failure {
+            error: ExecutionFailureStatus::MoveAbort(
+                MoveLocation {
+                    package: ObjectID::from_hex("0x42").unwrap(),
+                    module: Identifier::from_static("temperature"),
+                    function: 1,
+                    instruction: 12,
+                    function_name: Some(Identifier::from_static("push_reading")),
+                },
+                7,
+            ),
+            command: Some(0),
+        };
+
+        let mut bytes = alloc::vec![1, 12];
+        bytes.extend_from_slice(ObjectID::from_hex("0x42").unwrap().as_bytes());
+        bytes.push(11);
+        bytes.extend_from_slice(b"temperature");
+        bytes.extend_from_slice(&[1, 0, 12, 0, 1, 12]);
+        bytes.extend_from_slice(b"push_reading");
+        push_u64(&mut bytes, 7);
+        bytes.push(1);
+        push_u64(&mut bytes, 0);
+
+        assert_eq!(bcs::from_bytes::<ExecutionStatus>(&bytes).unwrap(), status);
+        assert!(!status.is_success());
+
+        let congestion = bcs::from_bytes::<ExecutionStatus>(&[1, 33, 0, 0]).unwrap();
+        assert_eq!(
+            congestion,
+            ExecutionStatus::Failure {
+                error: ExecutionFailureStatus::ExecutionCancelledDueToSharedObjectCongestion {
+                    congested_objects: alloc::vec![]
+                },
+                command: None,
+            }
+        );
+    }
+}
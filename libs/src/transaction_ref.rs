@@ -0,0 +1,475 @@
+//! Borrowed counterparts of the `transaction_types` needed to sign and submit a programmable
+//! transaction without a heap. Every type serializes to the same BCS bytes as its owned
+//! counterpart, so digests and signatures are interchangeable.
+//!
+//! Variable length fields are slices, which the caller keeps in stack buffers, `heapless::Vec`s
+//! or statics. Pure arguments are written with `bcs_stream::to_slice` or `to_heapless`.
+
+use crate::address::IotaAddress;
+use crate::base_types::{
+    Argument, BuilderError, Digest, EpochId, IdentStr, ObjectArg, ObjectID, ObjectRef,
+    SignableTransaction, TransactionExpiration, check_gas, transaction_digest,
+};
+use crate::bcs_stream::BcsStreamError;
+use serde::Serialize;
+
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub struct StructTagRef<'a> {
+    pub address: IotaAddress,
+    pub module: IdentStr<'a>,
+    pub name: IdentStr<'a>,
+    #[serde(rename = "type_args")]
+    pub type_params: &'a [TypeTagRef<'a>],
+}
+
+/// The variant order is the BCS enum tag and matches `TypeTag`, do not reorder.
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub enum TypeTagRef<'a> {
+    #[serde(rename = "bool")]
+    Bool,
+    #[serde(rename = "u8")]
+    U8,
+    #[serde(rename = "u64")]
+    U64,
+    #[serde(rename = "u128")]
+    U128,
+    #[serde(rename = "address")]
+    Address,
+    #[serde(rename = "signer")]
+    Signer,
+    #[serde(rename = "vector")]
+    Vector(&'a TypeTagRef<'a>),
+    #[serde(rename = "struct")]
+    Struct(&'a StructTagRef<'a>),
+    #[serde(rename = "u16")]
+    U16,
+    #[serde(rename = "u32")]
+    U32,
+    #[serde(rename = "u256")]
+    U256,
+}
+
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub enum CallArgRef<'a> {
+    /// BCS encoded value.
+    Pure(&'a [u8]),
+    Object(ObjectArg),
+}
+
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub struct ProgrammableMoveCallRef<'a> {
+    pub package: ObjectID,
+    pub module: IdentStr<'a>,
+    pub function: IdentStr<'a>,
+    pub type_arguments: &'a [TypeTagRef<'a>],
+    pub arguments: &'a [Argument],
+}
+
+/// The variant order is the BCS enum tag and matches `Command`, do not reorder.
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub enum CommandRef<'a> {
+    MoveCall(ProgrammableMoveCallRef<'a>),
+    TransferObjects(&'a [Argument], Argument),
+    SplitCoins(Argument, &'a [Argument]),
+    MergeCoins(Argument, &'a [Argument]),
+    Publish(&'a [&'a [u8]], &'a [ObjectID]),
+    MakeMoveVec(Option<TypeTagRef<'a>>, &'a [Argument]),
+    Upgrade(&'a [&'a [u8]], &'a [ObjectID], ObjectID, Argument),
+}
+
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub struct ProgrammableTransactionRef<'a> {
+    pub inputs: &'a [CallArgRef<'a>],
+    pub commands: &'a [CommandRef<'a>],
+}
+
+/// Only user transactions can be borrowed, system transactions are never built on devices.
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub enum TransactionKindRef<'a> {
+    ProgrammableTransaction1(ProgrammableTransactionRef<'a>),
+}
+
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub struct GasDataRef<'a> {
+    pub payment: &'a [ObjectRef],
+    pub owner: IotaAddress,
+    pub price: u64,
+    pub budget: u64,
+}
+
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub struct TransactionDataV1Ref<'a> {
+    pub kind: TransactionKindRef<'a>,
+    pub sender: IotaAddress,
+    pub gas_data: GasDataRef<'a>,
+    pub expiration: TransactionExpiration,
+}
+
+#[derive(Serialize, Debug, PartialEq, Hash, Eq, Clone, Copy)]
+pub enum TransactionDataRef<'a> {
+    V1(TransactionDataV1Ref<'a>),
+}
+
+impl SignableTransaction for TransactionDataRef<'_> {}
+
+impl TransactionDataRef<'_> {
+    /// The same digest `TransactionData::digest` returns for the owned transaction.
+    pub fn digest(&self) -> Result<Digest, BcsStreamError> {
+        transaction_digest(self)
+    }
+}
+
+/// The borrowed counterpart of `TransactionDataBuilder`, with the same gas checks.
+pub struct TransactionDataRefBuilder<'a> {
+    pt: ProgrammableTransactionRef<'a>,
+    sender: IotaAddress,
+    sponsor: Option<IotaAddress>,
+    payment: &'a [ObjectRef],
+    budget: Option<u64>,
+    price: Option<u64>,
+    expiration: TransactionExpiration,
+}
+
+impl<'a> TransactionDataRefBuilder<'a> {
+    pub fn new(pt: ProgrammableTransactionRef<'a>, sender: IotaAddress) -> Self {
+        Self {
+            pt,
+            sender,
+            sponsor: None,
+            payment: &[],
+            budget: None,
+            price: None,
+            expiration: TransactionExpiration::None,
+        }
+    }
+
+    pub fn sponsor(mut self, sponsor: IotaAddress) -> Self {
+        self.sponsor = Some(sponsor);
+        self
+    }
+
+    pub fn gas_payment(mut self, coins: &'a [ObjectRef]) -> Self {
+        self.payment = coins;
+        self
+    }
+
+    pub fn gas_budget(mut self, budget: u64) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    pub fn gas_price(mut self, price: u64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    /// The transaction is rejected once `epoch` has ended.
+    pub fn expiration(mut self, epoch: EpochId) -> Self {
+        self.expiration = TransactionExpiration::Epoch(epoch);
+        self
+    }
+
+    pub fn build(self) -> Result<TransactionDataRef<'a>, BuilderError> {
+        let (price, budget) = check_gas(self.payment, self.price, self.budget)?;
+
+        Ok(TransactionDataRef::V1(TransactionDataV1Ref {
+            kind: TransactionKindRef::ProgrammableTransaction1(self.pt),
+            sender: self.sender,
+            gas_data: GasDataRef {
+                payment: self.payment,
+                owner: self.sponsor.unwrap_or(self.sender),
+                price,
+                budget,
+            },
+            expiration: self.expiration,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::base_types::SequenceNumber;
+    use crate::bcs_stream::{to_heapless, to_slice};
+    use crate::transaction_types::{
+        CallArg, Command, GasData, Identifier, ProgrammableMoveCall, ProgrammableTransaction,
+        StructTag, TransactionData, TransactionDataV1, TransactionKind, TypeTag,
+    };
+    use core::str::FromStr;
+
+    const MODULE: IdentStr<'static> = IdentStr::from_static("temperature");
+    const FUNCTION: IdentStr<'static> = IdentStr::from_static("push_reading");
+
+    fn package() -> ObjectID {
+        ObjectID::from_hex("0x4a4a1a2e8b7bbeb2a7e77b6b1e5b7a1f5c8f1e8d9c0b1a2f3e4d5c6b7a8f9e0d")
+            .unwrap()
+    }
+
+    fn gas_coin() -> ObjectRef {
+        ObjectRef::new(
+            ObjectID::from_hex("0x6").unwrap(),
+            SequenceNumber::new(17),
+            Digest::new([3; 32]),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_borrowed_transaction_matches_owned_bcs() {
+        let sender = IotaAddress::new([1; 32]);
+        let sponsor = IotaAddress::new([2; 32]);
+        let coin_tag = StructTagRef {
+            address: IotaAddress::from_str("0x2").unwrap(),
+            module: IdentStr::from_static("coin"),
+            name: IdentStr::from_static("Coin"),
+            type_params: &[],
+        };
+
+        let sensor_id = to_heapless::<_, 1>(&123u8).unwrap();
+        let mut location = [0u8; 16];
+        let location_len = to_slice(&mut location, "ISS").unwrap();
+        let temperature = to_heapless::<_, 4>(&12345u32).unwrap();
+        let inputs = [
+            CallArgRef::Pure(&sensor_id),
+            CallArgRef::Pure(&location[..location_len]),
+            CallArgRef::Pure(&temperature),
+        ];
+        let arguments = [Argument::Input(0), Argument::Input(1), Argument::Input(2)];
+        let type_arguments = [TypeTagRef::Vector(&TypeTagRef::Struct(&coin_tag))];
+        let commands = [
+            CommandRef::MoveCall(ProgrammableMoveCallRef {
+                package: package(),
+                module: MODULE,
+                function: FUNCTION,
+                type_arguments: &type_arguments,
+                arguments: &arguments,
+            }),
+            CommandRef::TransferObjects(&[Argument::NestedResult(0, 1)], Argument::GasCoin),
+        ];
+        let payment = [gas_coin()];
+        let borrowed = TransactionDataRef::V1(TransactionDataV1Ref {
+            kind: TransactionKindRef::ProgrammableTransaction1(ProgrammableTransactionRef {
+                inputs: &inputs,
+                commands: &commands,
+            }),
+            sender,
+            gas_data: GasDataRef {
+                payment: &payment,
+                owner: sponsor,
+                price: 1000,
+                budget: 100_000_000,
+            },
+            expiration: TransactionExpiration::Epoch(12),
+        });
+
+        let owned = TransactionData::V1(TransactionDataV1 {
+            kind: TransactionKind::ProgrammableTransaction1(ProgrammableTransaction {
+                inputs: alloc::vec![
+                    CallArg::Pure(bcs::to_bytes(&123u8).unwrap()),
+                    CallArg::Pure(bcs::to_bytes("ISS").unwrap()),
+                    CallArg::Pure(bcs::to_bytes(&12345u32).unwrap()),
+                ],
+                commands: alloc::vec![
+                    Command::MoveCall(alloc::boxed::Box::new(ProgrammableMoveCall {
+                        package: package(),
+                        module: Identifier::from_static("temperature"),
+                        function: Identifier::from_static("push_reading"),
+                        type_arguments: alloc::vec![TypeTag::Vector(alloc::boxed::Box::new(
+                            TypeTag::Struct(alloc::boxed::Box::new(
+                                "0x2::coin::Coin".parse::<StructTag>().unwrap()
+                            ))
+                        ))],
+                        arguments: arguments.to_vec(),
+                    })),
+                    Command::TransferObjects(
+                        alloc::vec![Argument::NestedResult(0, 1)],
+                        Argument::GasCoin
+                    ),
+                ],
+            }),
+            sender,
+            gas_data: GasData {
+                payment: alloc::vec![gas_coin()],
+                owner: sponsor,
+                price: 1000,
+                budget: 100_000_000,
+            },
+            expiration: TransactionExpiration::Epoch(12),
+        });
+
+        assert_eq!(
+            bcs::to_bytes(&borrowed).unwrap(),
+            bcs::to_bytes(&owned).unwrap()
+        );
+        assert_eq!(borrowed.digest().unwrap(), owned.digest().unwrap());
+    }
+
+    fn assert_same_bcs<B: Serialize, O: Serialize>(borrowed: &B, owned: &O) {
+        assert_eq!(
+            bcs::to_bytes(borrowed).unwrap(),
+            bcs::to_bytes(owned).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_borrowed_variants_match_owned_bcs() {
+        let shared = ObjectArg::SharedObject {
+            id: ObjectID::from_hex("0x5").unwrap(),
+            initial_shared_version: SequenceNumber::new(1),
+            mutable: true,
+        };
+        let receiving = ObjectArg::Receiving(ObjectRef::new(
+            ObjectID::from_hex("0x7").unwrap(),
+            SequenceNumber::new(4),
+            Digest::new([9; 32]),
+        ));
+        let inputs = [
+            CallArgRef::Object(ObjectArg::ImmOrOwnedObject(gas_coin())),
+            CallArgRef::Object(shared),
+            CallArgRef::Object(receiving),
+        ];
+        let owned_inputs = alloc::vec![
+            CallArg::Object(ObjectArg::ImmOrOwnedObject(gas_coin())),
+            CallArg::Object(shared),
+            CallArg::Object(receiving),
+        ];
+
+        // 0x2::table::Table<u64, vector<u8>>
+        let type_params = [TypeTagRef::U64, TypeTagRef::Vector(&TypeTagRef::U8)];
+        let table_tag = StructTagRef {
+            address: IotaAddress::from_str("0x2").unwrap(),
+            module: IdentStr::from_static("table"),
+            name: IdentStr::from_static("Table"),
+            type_params: &type_params,
+        };
+        let owned_table_tag = "0x2::table::Table<u64, vector<u8>>"
+            .parse::<StructTag>()
+            .unwrap();
+        assert_same_bcs(&table_tag, &owned_table_tag);
+
+        let module_a: &[u8] = &[0xa1, 0x1c, 0xeb, 0x0b];
+        let module_b: &[u8] = &[1, 2, 3];
+        let modules = [module_a, module_b];
+        let dependencies = [
+            ObjectID::from_hex("0x1").unwrap(),
+            ObjectID::from_hex("0x2").unwrap(),
+        ];
+        let coins = [Argument::Input(0), Argument::Result(1)];
+        let amounts = [Argument::Input(1), Argument::Input(2)];
+        let commands = [
+            CommandRef::SplitCoins(Argument::GasCoin, &amounts),
+            CommandRef::MergeCoins(Argument::Input(0), &coins),
+            CommandRef::MakeMoveVec(Some(TypeTagRef::Struct(&table_tag)), &coins),
+            CommandRef::MakeMoveVec(None, &amounts),
+            CommandRef::Publish(&modules, &dependencies),
+            CommandRef::Upgrade(&modules, &dependencies, package(), Argument::Result(0)),
+        ];
+        let owned_modules = alloc::vec![module_a.to_vec(), module_b.to_vec()];
+        let owned_commands = alloc::vec![
+            Command::SplitCoins(Argument::GasCoin, amounts.to_vec()),
+            Command::MergeCoins(Argument::Input(0), coins.to_vec()),
+            Command::MakeMoveVec(
+                Some(TypeTag::Struct(alloc::boxed::Box::new(owned_table_tag))),
+                coins.to_vec()
+            ),
+            Command::MakeMoveVec(None, amounts.to_vec()),
+            Command::Publish(owned_modules.clone(), dependencies.to_vec()),
+            Command::Upgrade(
+                owned_modules,
+                dependencies.to_vec(),
+                package(),
+                Argument::Result(0)
+            ),
+        ];
+
+        for (borrowed, owned) in inputs.iter().zip(&owned_inputs) {
+            assert_same_bcs(borrowed, owned);
+        }
+        for (borrowed, owned) in commands.iter().zip(&owned_commands) {
+            assert_same_bcs(borrowed, owned);
+        }
+        assert_same_bcs(
+            &ProgrammableTransactionRef {
+                inputs: &inputs,
+                commands: &commands,
+            },
+            &ProgrammableTransaction {
+                inputs: owned_inputs,
+                commands: owned_commands,
+            },
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ref_builder_checks_gas() {
+        let sender = IotaAddress::new([1; 32]);
+        let pt = ProgrammableTransactionRef {
+            inputs: &[],
+            commands: &[],
+        };
+        let payment = [gas_coin()];
+        let duplicate = [gas_coin(), gas_coin()];
+
+        assert_eq!(
+            TransactionDataRefBuilder::new(pt, sender)
+                .gas_payment(&payment)
+                .gas_budget(100)
+                .build(),
+            Err(BuilderError::MissingGasPrice)
+        );
+        assert_eq!(
+            TransactionDataRefBuilder::new(pt, sender)
+                .gas_payment(&payment)
+                .gas_price(1000)
+                .build(),
+            Err(BuilderError::MissingGasBudget)
+        );
+        assert_eq!(
+            TransactionDataRefBuilder::new(pt, sender)
+                .gas_payment(&payment)
+                .gas_price(1000)
+                .gas_budget(999)
+                .build(),
+            Err(BuilderError::GasBudgetTooLow {
+                budget: 999,
+                price: 1000
+            })
+        );
+        assert_eq!(
+            TransactionDataRefBuilder::new(pt, sender)
+                .gas_price(1000)
+                .gas_budget(1000)
+                .build(),
+            Err(BuilderError::NoGasPayment)
+        );
+        assert_eq!(
+            TransactionDataRefBuilder::new(pt, sender)
+                .gas_payment(&duplicate)
+                .gas_price(1000)
+                .gas_budget(1000)
+                .build(),
+            Err(BuilderError::DuplicateGasPayment(gas_coin().object_id))
+        );
+
+        let tx = TransactionDataRefBuilder::new(pt, sender)
+            .gas_payment(&payment)
+            .gas_price(1000)
+            .gas_budget(1000)
+            .build()
+            .unwrap();
+        let TransactionDataRef::V1(v1) = tx;
+        assert_eq!(v1.gas_data.owner, sender);
+    }
+
+    #[tokio::test]
+    async fn test_ident_str_validation() {
+        assert!(IdentStr::new("push_reading").is_ok());
+        assert_eq!(
+            IdentStr::new("1abc"),
+            Err(crate::base_types::IdentifierError::InvalidCharacter(0))
+        );
+        assert_eq!(
+            Identifier::from_static("coin").as_ident_str(),
+            IdentStr::from_static("coin")
+        );
+    }
+}
@@ -1,54 +1,28 @@
-use blake2b_simd::Hash;
-use serde::{Deserialize, Serialize};
+use crate::address::{AddressError, IotaAddress};
+use crate::bcs_stream::{BcsStreamError, serialized_size};
+use crate::crypto::{
+    CryptoError, Intent, MAX_MULTISIG_SIZE, MULTISIG_FLAG, MultiSig, MultiSigBytes,
+    SerializedSignature,
+};
+use crate::encoding::MultiSigBase64Signature;
+use base64::prelude::*;
+use core::fmt::{Debug, Display, Formatter};
+use core::str::FromStr;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 extern crate alloc;
-pub const IOTA_ADDRESS_LENGTH: usize = 32;
 
-#[derive(
-    Debug, Eq, Default, PartialEq, Ord, PartialOrd, Copy, Clone, Hash, Serialize, Deserialize,
-)]
-pub struct ObjectID([u8; IOTA_ADDRESS_LENGTH]);
+// Base64 needs 4 characters per started 3 bytes.
+const MAX_BASE64_SIGNATURE_LENGTH: usize = MAX_MULTISIG_SIZE.div_ceil(3) * 4;
 
-impl ObjectID {
-    pub const fn new(obj_id: [u8; IOTA_ADDRESS_LENGTH]) -> Self {
-        Self(obj_id)
-    }
-}
-
-impl From<blake2b_simd::Hash> for ObjectID {
-    fn from(hash: Hash) -> Self {
-        Self::new(<[u8; 32]>::try_from(hash.as_bytes()).unwrap())
-    }
-}
-
-#[derive(
-    Eq, PartialEq, Ord, PartialOrd, Clone, Copy, Hash, Default, Debug, Serialize, Deserialize,
-)]
-pub struct SequenceNumber(u64);
-
-impl SequenceNumber {
-    pub fn new(sequence_number: u64) -> Self {
-        Self(sequence_number)
-    }
-}
-
-#[derive(
-    Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug,
-)]
-pub struct Digest(#[serde(with = "serde_bytes")] [u8; IOTA_ADDRESS_LENGTH]);
-
-impl Digest {
-    pub const ZERO: Self = Digest([0; IOTA_ADDRESS_LENGTH]);
-
-    pub const fn new(digest: [u8; IOTA_ADDRESS_LENGTH]) -> Self {
-        Self(digest)
-    }
-}
-
-pub type ObjectRef = (ObjectID, SequenceNumber, Digest);
+pub use crate::base_types::{
+    Argument, Digest, DigestError, EpochId, IdentStr, IdentifierError, ObjectArg, ObjectID,
+    ObjectRef, SequenceNumber, SignableTransaction, TransactionExpiration,
+};
+use crate::base_types::{transaction_digest, validate_identifier};
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Hash, Eq, Clone, PartialOrd, Ord)]
 pub struct StructTag {
-    pub address: ObjectID,
+    pub address: IotaAddress,
     pub module: Identifier,
     pub name: Identifier,
     #[serde(rename = "type_args", alias = "type_params")]
@@ -83,24 +57,263 @@ pub enum TypeTag {
     U256,
 }
 
+/// A Move module, function or struct name: `[a-zA-Z][a-zA-Z0-9_]*` or `_[a-zA-Z0-9_]+`.
+///
+/// Names known at compile time should use `from_static`, which borrows instead of allocating.
 #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, Deserialize)]
-pub struct Identifier(alloc::boxed::Box<str>);
+#[serde(try_from = "alloc::string::String")]
+pub struct Identifier(alloc::borrow::Cow<'static, str>);
 
 impl Identifier {
-    pub fn new(identifier: alloc::boxed::Box<str>) -> Identifier {
-        Self(identifier)
+    pub fn new(identifier: alloc::boxed::Box<str>) -> Result<Identifier, IdentifierError> {
+        validate_identifier(&identifier)?;
+        Ok(Self(alloc::borrow::Cow::Owned(identifier.into())))
+    }
+
+    /// Panics if `identifier` is invalid, which fails the build when used in a `const`.
+    pub const fn from_static(identifier: &'static str) -> Identifier {
+        if validate_identifier(identifier).is_err() {
+            panic!("invalid Move identifier");
+        }
+        Self(alloc::borrow::Cow::Borrowed(identifier))
+    }
+
+    pub const fn is_valid(identifier: &str) -> bool {
+        validate_identifier(identifier).is_ok()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn as_ident_str(&self) -> IdentStr<'_> {
+        IdentStr::from_validated(&self.0)
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
-pub enum ObjectArg {
-    ImmOrOwnedObject(ObjectRef),
-    SharedObject {
-        id: ObjectID,
-        initial_shared_version: SequenceNumber,
-        mutable: bool,
-    },
-    Receiving(ObjectRef),
+impl TryFrom<alloc::string::String> for Identifier {
+    type Error = IdentifierError;
+
+    fn try_from(identifier: alloc::string::String) -> Result<Self, Self::Error> {
+        Self::new(identifier.into_boxed_str())
+    }
+}
+
+impl FromStr for Identifier {
+    type Err = IdentifierError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s.into())
+    }
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeTagParseError {
+    UnexpectedEnd,
+    /// Byte offset of the first character that could not be parsed.
+    UnexpectedToken(usize),
+    InvalidAddress(AddressError),
+    InvalidIdentifier(usize),
+    /// A struct type was expected, but the string names a primitive or vector type.
+    NotAStruct,
+}
+
+impl Display for TypeTagParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TypeTagParseError::UnexpectedEnd => write!(f, "unexpected end of type"),
+            TypeTagParseError::UnexpectedToken(pos) => write!(f, "unexpected token at {}", pos),
+            TypeTagParseError::InvalidAddress(err) => write!(f, "{}", err),
+            TypeTagParseError::InvalidIdentifier(pos) => {
+                write!(f, "invalid identifier at {}", pos)
+            }
+            TypeTagParseError::NotAStruct => write!(f, "not a struct type"),
+        }
+    }
+}
+
+/// Recursive descent parser for canonical type strings such as
+/// `0x2::coin::Coin<0x2::iota::IOTA>` or `vector<u8>`.
+struct TypeTagParser<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> TypeTagParser<'a> {
+    fn parse<T>(
+        input: &'a str,
+        parse: impl FnOnce(&mut Self) -> Result<T, TypeTagParseError>,
+    ) -> Result<T, TypeTagParseError> {
+        let mut parser = Self { input, pos: 0 };
+        let value = parse(&mut parser)?;
+        parser.skip_whitespace();
+        if parser.pos != input.len() {
+            return Err(TypeTagParseError::UnexpectedToken(parser.pos));
+        }
+        Ok(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.input[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.input[self.pos..].starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), TypeTagParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else if self.pos == self.input.len() {
+            Err(TypeTagParseError::UnexpectedEnd)
+        } else {
+            Err(TypeTagParseError::UnexpectedToken(self.pos))
+        }
+    }
+
+    /// The next run of `[A-Za-z0-9_]`, which covers keywords, identifiers and hex addresses.
+    fn word(&mut self) -> Result<(usize, &'a str), TypeTagParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let rest = &self.input[start..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        if len == 0 {
+            return Err(if rest.is_empty() {
+                TypeTagParseError::UnexpectedEnd
+            } else {
+                TypeTagParseError::UnexpectedToken(start)
+            });
+        }
+        self.pos += len;
+        Ok((start, &rest[..len]))
+    }
+
+    fn identifier(&mut self) -> Result<Identifier, TypeTagParseError> {
+        let (start, word) = self.word()?;
+        Identifier::new(word.into()).map_err(|_| TypeTagParseError::InvalidIdentifier(start))
+    }
+
+    fn type_tag(&mut self) -> Result<TypeTag, TypeTagParseError> {
+        let (start, word) = self.word()?;
+        Ok(match word {
+            "bool" => TypeTag::Bool,
+            "u8" => TypeTag::U8,
+            "u16" => TypeTag::U16,
+            "u32" => TypeTag::U32,
+            "u64" => TypeTag::U64,
+            "u128" => TypeTag::U128,
+            "u256" => TypeTag::U256,
+            "address" => TypeTag::Address,
+            "signer" => TypeTag::Signer,
+            "vector" => {
+                self.expect("<")?;
+                let element = self.type_tag()?;
+                self.expect(">")?;
+                TypeTag::Vector(alloc::boxed::Box::new(element))
+            }
+            address if address.starts_with("0x") => {
+                TypeTag::Struct(alloc::boxed::Box::new(self.struct_tag_after(address)?))
+            }
+            _ => return Err(TypeTagParseError::UnexpectedToken(start)),
+        })
+    }
+
+    fn struct_tag_after(&mut self, address: &str) -> Result<StructTag, TypeTagParseError> {
+        let address = IotaAddress::from_str(address).map_err(TypeTagParseError::InvalidAddress)?;
+        self.expect("::")?;
+        let module = self.identifier()?;
+        self.expect("::")?;
+        let name = self.identifier()?;
+
+        let mut type_params = alloc::vec::Vec::new();
+        if self.eat("<") {
+            loop {
+                type_params.push(self.type_tag()?);
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(">")?;
+        }
+
+        Ok(StructTag {
+            address,
+            module,
+            name,
+            type_params,
+        })
+    }
+}
+
+impl FromStr for TypeTag {
+    type Err = TypeTagParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TypeTagParser::parse(s, TypeTagParser::type_tag)
+    }
+}
+
+impl FromStr for StructTag {
+    type Err = TypeTagParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match TypeTag::from_str(s)? {
+            TypeTag::Struct(tag) => Ok(*tag),
+            _ => Err(TypeTagParseError::NotAStruct),
+        }
+    }
+}
+
+/// Formats the canonical type string, with the address shortened to `0x2` form.
+impl Display for StructTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let hex = self.address.to_hex();
+        let short = hex.trim_start_matches('0');
+        let short = if short.is_empty() { "0" } else { short };
+        write!(f, "0x{}::{}::{}", short, self.module, self.name)?;
+
+        if let Some((first, rest)) = self.type_params.split_first() {
+            write!(f, "<{}", first)?;
+            for type_param in rest {
+                write!(f, ", {}", type_param)?;
+            }
+            f.write_str(">")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for TypeTag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TypeTag::Bool => f.write_str("bool"),
+            TypeTag::U8 => f.write_str("u8"),
+            TypeTag::U16 => f.write_str("u16"),
+            TypeTag::U32 => f.write_str("u32"),
+            TypeTag::U64 => f.write_str("u64"),
+            TypeTag::U128 => f.write_str("u128"),
+            TypeTag::U256 => f.write_str("u256"),
+            TypeTag::Address => f.write_str("address"),
+            TypeTag::Signer => f.write_str("signer"),
+            TypeTag::Vector(element) => write!(f, "vector<{}>", element),
+            TypeTag::Struct(tag) => write!(f, "{}", tag),
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
@@ -118,14 +331,6 @@ pub struct ProgrammableMoveCall {
     pub arguments: alloc::vec::Vec<Argument>,
 }
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
-pub enum Argument {
-    GasCoin,
-    Input(u16),
-    Result(u16),
-    NestedResult(u16, u16),
-}
-
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum Command {
     MoveCall(alloc::boxed::Box<ProgrammableMoveCall>),
@@ -151,32 +356,584 @@ pub struct ProgrammableTransaction {
     pub commands: alloc::vec::Vec<Command>,
 }
 
+/// Protocol limits checked by `ProgrammableTransaction::validate`.
+/// Field names follow the node's protocol config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolLimits {
+    /// Object and receiving inputs. All inputs, pure ones included, may be up to twice this.
+    pub max_input_objects: usize,
+    /// Exclusive, a transaction must have fewer commands than this.
+    pub max_programmable_tx_commands: usize,
+    /// Per command, including the coin of `SplitCoins` or the target of `MergeCoins`.
+    pub max_arguments: usize,
+    pub max_type_arguments: usize,
+    pub max_pure_argument_size: usize,
+    pub max_tx_size_bytes: usize,
+}
+
+impl ProtocolLimits {
+    /// The mainnet protocol configuration.
+    pub const MAINNET: Self = Self {
+        max_input_objects: 2048,
+        max_programmable_tx_commands: 1024,
+        max_arguments: 512,
+        max_type_arguments: 16,
+        max_pure_argument_size: 16 * 1024,
+        max_tx_size_bytes: 128 * 1024,
+    };
+}
+
+impl Default for ProtocolLimits {
+    fn default() -> Self {
+        Self::MAINNET
+    }
+}
+
+/// A problem `ProgrammableTransaction::validate` found. `command` and `input` are indices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    TooManyInputObjects {
+        count: usize,
+        max: usize,
+    },
+    TooManyInputs {
+        count: usize,
+        max: usize,
+    },
+    TooManyCommands {
+        count: usize,
+        max: usize,
+    },
+    TooManyArguments {
+        command: usize,
+        count: usize,
+        max: usize,
+    },
+    TooManyTypeArguments {
+        command: usize,
+        count: usize,
+        max: usize,
+    },
+    PureArgumentTooLarge {
+        input: usize,
+        size: usize,
+        max: usize,
+    },
+    TransactionTooLarge {
+        size: usize,
+        max: usize,
+    },
+    /// `Argument::Input` past the end of the inputs.
+    InvalidInput {
+        command: usize,
+        input: u16,
+    },
+    /// A `Result` or `NestedResult` of the command itself or a later one.
+    InvalidResult {
+        command: usize,
+        result: u16,
+    },
+    /// A `NestedResult` past the number of results of `SplitCoins`.
+    InvalidNestedResult {
+        command: usize,
+        result: u16,
+        index: u16,
+    },
+    /// `GasCoin` used where it would be taken by value or is not a coin, e.g. as a merge source.
+    InvalidGasCoinUsage {
+        command: usize,
+    },
+    /// A command with an empty list of coins, amounts or objects.
+    EmptyArguments {
+        command: usize,
+    },
+    SerializationFailed(BcsStreamError),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ValidationError::TooManyInputObjects { count, max } => {
+                write!(f, "{} input objects, max {}", count, max)
+            }
+            ValidationError::TooManyInputs { count, max } => {
+                write!(f, "{} inputs, max {}", count, max)
+            }
+            ValidationError::TooManyCommands { count, max } => {
+                write!(f, "{} commands, must be less than {}", count, max)
+            }
+            ValidationError::TooManyArguments {
+                command,
+                count,
+                max,
+            } => write!(f, "command {}: {} arguments, max {}", command, count, max),
+            ValidationError::TooManyTypeArguments {
+                command,
+                count,
+                max,
+            } => write!(
+                f,
+                "command {}: {} type arguments, max {}",
+                command, count, max
+            ),
+            ValidationError::PureArgumentTooLarge { input, size, max } => {
+                write!(f, "input {}: {} bytes, max {}", input, size, max)
+            }
+            ValidationError::TransactionTooLarge { size, max } => {
+                write!(f, "transaction is {} bytes, max {}", size, max)
+            }
+            ValidationError::InvalidInput { command, input } => {
+                write!(f, "command {}: input {} does not exist", command, input)
+            }
+            ValidationError::InvalidResult { command, result } => {
+                write!(f, "command {}: result {} is not available", command, result)
+            }
+            ValidationError::InvalidNestedResult {
+                command,
+                result,
+                index,
+            } => write!(
+                f,
+                "command {}: result {} has no value {}",
+                command, result, index
+            ),
+            ValidationError::InvalidGasCoinUsage { command } => {
+                write!(f, "command {}: invalid use of the gas coin", command)
+            }
+            ValidationError::EmptyArguments { command } => {
+                write!(f, "command {}: empty argument list", command)
+            }
+            ValidationError::SerializationFailed(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl ProgrammableTransaction {
+    /// Checks what the node would reject before executing: argument references, gas coin usage
+    /// and protocol limits. Returns every problem found, not just the first.
+    pub fn validate(&self) -> Result<(), alloc::vec::Vec<ValidationError>> {
+        self.validate_with_limits(&ProtocolLimits::MAINNET)
+    }
+
+    /// The size check only covers the programmable transaction, a lower bound of what the node
+    /// measures. `TransactionData::validate` checks the size of the whole transaction.
+    pub fn validate_with_limits(
+        &self,
+        limits: &ProtocolLimits,
+    ) -> Result<(), alloc::vec::Vec<ValidationError>> {
+        let mut errors = alloc::vec::Vec::new();
+        self.check(limits, &mut errors);
+        check_size(self, limits, &mut errors);
+        into_result(errors)
+    }
+
+    fn check(&self, limits: &ProtocolLimits, errors: &mut alloc::vec::Vec<ValidationError>) {
+        let input_objects = self
+            .inputs
+            .iter()
+            .filter(|input| matches!(input, CallArg::Object(_)))
+            .count();
+        if input_objects > limits.max_input_objects {
+            errors.push(ValidationError::TooManyInputObjects {
+                count: input_objects,
+                max: limits.max_input_objects,
+            });
+        }
+        let max_inputs = limits.max_input_objects.saturating_mul(2);
+        if self.inputs.len() > max_inputs {
+            errors.push(ValidationError::TooManyInputs {
+                count: self.inputs.len(),
+                max: max_inputs,
+            });
+        }
+        if self.commands.len() >= limits.max_programmable_tx_commands {
+            errors.push(ValidationError::TooManyCommands {
+                count: self.commands.len(),
+                max: limits.max_programmable_tx_commands,
+            });
+        }
+        for (input, call_arg) in self.inputs.iter().enumerate() {
+            if let CallArg::Pure(bytes) = call_arg
+                && bytes.len() > limits.max_pure_argument_size
+            {
+                errors.push(ValidationError::PureArgumentTooLarge {
+                    input,
+                    size: bytes.len(),
+                    max: limits.max_pure_argument_size,
+                });
+            }
+        }
+        for (index, command) in self.commands.iter().enumerate() {
+            self.validate_command(index, command, limits, errors);
+        }
+    }
+
+    fn validate_command(
+        &self,
+        command_index: usize,
+        command: &Command,
+        limits: &ProtocolLimits,
+        errors: &mut alloc::vec::Vec<ValidationError>,
+    ) {
+        // (argument, whether GasCoin is allowed there)
+        let mut arguments = alloc::vec::Vec::new();
+        let mut type_argument_count = 0;
+        // The list that must not be empty, if the command has one.
+        let mut required: Option<&[Argument]> = None;
+
+        match command {
+            Command::MoveCall(call) => {
+                type_argument_count = call.type_arguments.len();
+                arguments.extend(call.arguments.iter().map(|arg| (arg, true)));
+            }
+            Command::TransferObjects(objects, recipient) => {
+                required = Some(objects);
+                arguments.extend(objects.iter().map(|arg| (arg, true)));
+                arguments.push((recipient, false));
+            }
+            Command::SplitCoins(coin, amounts) => {
+                required = Some(amounts);
+                arguments.push((coin, true));
+                arguments.extend(amounts.iter().map(|arg| (arg, false)));
+            }
+            Command::MergeCoins(target, coins) => {
+                required = Some(coins);
+                arguments.push((target, true));
+                arguments.extend(coins.iter().map(|arg| (arg, false)));
+            }
+            Command::Publish(_, _) => {}
+            Command::MakeMoveVec(type_tag, elements) => {
+                type_argument_count = type_tag.iter().count();
+                if type_tag.is_none() {
+                    required = Some(elements);
+                }
+                arguments.extend(elements.iter().map(|arg| (arg, false)));
+            }
+            Command::Upgrade(_, _, _, ticket) => arguments.push((ticket, false)),
+        }
+
+        if required.is_some_and(|arguments| arguments.is_empty()) {
+            errors.push(ValidationError::EmptyArguments {
+                command: command_index,
+            });
+        }
+        if arguments.len() > limits.max_arguments {
+            errors.push(ValidationError::TooManyArguments {
+                command: command_index,
+                count: arguments.len(),
+                max: limits.max_arguments,
+            });
+        }
+        if type_argument_count > limits.max_type_arguments {
+            errors.push(ValidationError::TooManyTypeArguments {
+                command: command_index,
+                count: type_argument_count,
+                max: limits.max_type_arguments,
+            });
+        }
+
+        let mut gas_coin_reported = false;
+        for (argument, gas_coin_allowed) in arguments {
+            let error = match *argument {
+                Argument::GasCoin if !gas_coin_allowed && !gas_coin_reported => {
+                    gas_coin_reported = true;
+                    Some(ValidationError::InvalidGasCoinUsage {
+                        command: command_index,
+                    })
+                }
+                Argument::GasCoin => None,
+                Argument::Input(input) => {
+                    (input as usize >= self.inputs.len()).then_some(ValidationError::InvalidInput {
+                        command: command_index,
+                        input,
+                    })
+                }
+                Argument::Result(result) => {
+                    (result as usize >= command_index).then_some(ValidationError::InvalidResult {
+                        command: command_index,
+                        result,
+                    })
+                }
+                Argument::NestedResult(result, index) => {
+                    self.validate_nested_result(command_index, result, index)
+                }
+            };
+            errors.extend(error);
+        }
+    }
+
+    fn validate_nested_result(
+        &self,
+        command: usize,
+        result: u16,
+        index: u16,
+    ) -> Option<ValidationError> {
+        if result as usize >= command {
+            return Some(ValidationError::InvalidResult { command, result });
+        }
+        // Only `SplitCoins` has a result count known without the Move signatures.
+        match &self.commands[result as usize] {
+            Command::SplitCoins(_, amounts) if index as usize >= amounts.len() => {
+                Some(ValidationError::InvalidNestedResult {
+                    command,
+                    result,
+                    index,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+fn check_size<T: Serialize + ?Sized>(
+    value: &T,
+    limits: &ProtocolLimits,
+    errors: &mut alloc::vec::Vec<ValidationError>,
+) {
+    match serialized_size(value) {
+        Ok(size) if size > limits.max_tx_size_bytes => {
+            errors.push(ValidationError::TransactionTooLarge {
+                size,
+                max: limits.max_tx_size_bytes,
+            });
+        }
+        Ok(_) => {}
+        Err(err) => errors.push(ValidationError::SerializationFailed(err)),
+    }
+}
+
+fn into_result(
+    errors: alloc::vec::Vec<ValidationError>,
+) -> Result<(), alloc::vec::Vec<ValidationError>> {
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// The variant order is the BCS enum tag and must match the node, do not reorder.
+/// All kinds except `ProgrammableTransaction1` are system transactions created by validators.
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub enum TransactionKind {
     ProgrammableTransaction1(ProgrammableTransaction),
-    // Other Kinds not implemented
+    /// Initializes the chain state, only valid as the first transaction of checkpoint 0.
+    Genesis(GenesisTransaction),
+    ConsensusCommitPrologue(ConsensusCommitPrologueV1),
+    /// Updates the JWKs used for zkLogin.
+    AuthenticatorStateUpdate(AuthenticatorStateUpdateV1),
+    /// Closes out the current epoch and starts the next one.
+    /// IOTA has no standalone `ChangeEpoch` kind, it is one of these operations.
+    EndOfEpoch(alloc::vec::Vec<EndOfEpochTransactionKind>),
+    RandomnessStateUpdate(RandomnessStateUpdate),
+}
+
+impl TransactionKind {
+    pub fn is_system_transaction(&self) -> bool {
+        !matches!(self, TransactionKind::ProgrammableTransaction1(_))
+    }
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct GasData {
     pub payment: alloc::vec::Vec<ObjectRef>,
-    pub owner: ObjectID,
+    pub owner: IotaAddress,
     pub price: u64,
     pub budget: u64,
 }
 
-pub type EpochId = u64;
+pub type ProtocolVersion = u64;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct GenesisTransaction {
+    pub objects: alloc::vec::Vec<GenesisObject>,
+    pub events: alloc::vec::Vec<Event>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum GenesisObject {
+    RawObject { data: ObjectData, owner: Owner },
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum ObjectData {
+    Move(MoveObject),
+    Package(MovePackage),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct MoveObject {
+    pub type_: MoveObjectType,
+    pub version: SequenceNumber,
+    /// BCS bytes of the Move struct, starting with its `UID`.
+    pub contents: alloc::vec::Vec<u8>,
+}
+
+/// Struct types with a compact encoding for the most common objects.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum MoveObjectType {
+    Other(StructTag),
+    /// `0x2::coin::Coin<0x2::iota::IOTA>`
+    GasCoin,
+    /// `0x3::staking_pool::StakedIota`
+    StakedIota,
+    /// `0x2::coin::Coin<T>` for any other `T`.
+    Coin(TypeTag),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct MovePackage {
+    pub id: ObjectID,
+    pub version: SequenceNumber,
+    pub module_map: alloc::collections::BTreeMap<alloc::string::String, alloc::vec::Vec<u8>>,
+    pub type_origin_table: alloc::vec::Vec<TypeOrigin>,
+    pub linkage_table: alloc::collections::BTreeMap<ObjectID, UpgradeInfo>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct TypeOrigin {
+    pub module_name: alloc::string::String,
+    pub datatype_name: alloc::string::String,
+    pub package: ObjectID,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub struct UpgradeInfo {
+    pub upgraded_id: ObjectID,
+    pub upgraded_version: SequenceNumber,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
+pub enum Owner {
+    AddressOwner(IotaAddress),
+    /// Owned by another object, e.g. a dynamic field.
+    ObjectOwner(IotaAddress),
+    Shared {
+        initial_shared_version: SequenceNumber,
+    },
+    Immutable,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct Event {
+    pub package_id: ObjectID,
+    pub transaction_module: Identifier,
+    pub sender: IotaAddress,
+    pub type_: StructTag,
+    /// BCS bytes of the event struct.
+    pub contents: alloc::vec::Vec<u8>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct ConsensusCommitPrologueV1 {
+    pub epoch: EpochId,
+    pub round: u64,
+    /// Index of the sub dag within the consensus commit, if it was split.
+    pub sub_dag_index: Option<u64>,
+    pub commit_timestamp_ms: u64,
+    pub consensus_commit_digest: Digest,
+    pub consensus_determined_version_assignments: ConsensusDeterminedVersionAssignments,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum ConsensusDeterminedVersionAssignments {
+    /// The shared object versions assigned to transactions cancelled by consensus.
+    CancelledTransactions(alloc::vec::Vec<(Digest, alloc::vec::Vec<(ObjectID, SequenceNumber)>)>),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct AuthenticatorStateUpdateV1 {
+    pub epoch: EpochId,
+    pub round: u64,
+    pub new_active_jwks: alloc::vec::Vec<ActiveJwk>,
+    pub authenticator_obj_initial_shared_version: SequenceNumber,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct ActiveJwk {
+    pub jwk_id: JwkId,
+    pub jwk: Jwk,
+    pub epoch: EpochId,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct JwkId {
+    pub iss: alloc::string::String,
+    pub kid: alloc::string::String,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: alloc::string::String,
+    pub e: alloc::string::String,
+    pub n: alloc::string::String,
+    pub alg: alloc::string::String,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct RandomnessStateUpdate {
+    pub epoch: EpochId,
+    pub randomness_round: u64,
+    pub random_bytes: alloc::vec::Vec<u8>,
+    pub randomness_obj_initial_shared_version: SequenceNumber,
+}
+
+/// The variant order is the BCS enum tag, do not reorder.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub enum EndOfEpochTransactionKind {
+    ChangeEpoch(ChangeEpoch),
+    ChangeEpochV2(ChangeEpochV2),
+    AuthenticatorStateCreate,
+    AuthenticatorStateExpire(AuthenticatorStateExpire),
+    /// The chain identifier, the digest of the genesis checkpoint.
+    BridgeStateCreate(Digest),
+    BridgeCommitteeInit(SequenceNumber),
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct ChangeEpoch {
+    /// The next epoch.
+    pub epoch: EpochId,
+    pub protocol_version: ProtocolVersion,
+    pub storage_charge: u64,
+    pub computation_charge: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
+    pub epoch_start_timestamp_ms: u64,
+    /// System packages to write before the new epoch starts:
+    /// `(version, modules, dependencies)`.
+    pub system_packages: alloc::vec::Vec<SystemPackage>,
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct ChangeEpochV2 {
+    pub epoch: EpochId,
+    pub protocol_version: ProtocolVersion,
+    pub storage_charge: u64,
+    pub computation_charge: u64,
+    pub computation_charge_burned: u64,
+    pub storage_rebate: u64,
+    pub non_refundable_storage_fee: u64,
+    pub epoch_start_timestamp_ms: u64,
+    pub system_packages: alloc::vec::Vec<SystemPackage>,
+}
+
+pub type SystemPackage = (
+    SequenceNumber,
+    alloc::vec::Vec<alloc::vec::Vec<u8>>,
+    alloc::vec::Vec<ObjectID>,
+);
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
-pub enum TransactionExpiration {
-    None,
-    Epoch(EpochId),
+pub struct AuthenticatorStateExpire {
+    pub min_epoch: EpochId,
+    pub authenticator_obj_initial_shared_version: SequenceNumber,
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
 pub struct TransactionDataV1 {
     pub kind: TransactionKind,
-    pub sender: ObjectID,
+    pub sender: IotaAddress,
     pub gas_data: GasData,
     pub expiration: TransactionExpiration,
 }
@@ -186,6 +943,224 @@ pub enum TransactionData {
     V1(TransactionDataV1),
 }
 
+impl SignableTransaction for TransactionData {}
+
+impl TransactionData {
+    /// The transaction digest as reported by the node: `blake2b("TransactionData::" || bcs(self))`.
+    /// Known before submission, so it can be logged or persisted even if the response is lost.
+    pub fn digest(&self) -> Result<Digest, BcsStreamError> {
+        transaction_digest(self)
+    }
+
+    /// Runs `ProgrammableTransaction::validate` on user transactions and checks the size of the
+    /// whole transaction. Signatures are not included, they add about 100 bytes per signer.
+    pub fn validate(&self) -> Result<(), alloc::vec::Vec<ValidationError>> {
+        self.validate_with_limits(&ProtocolLimits::MAINNET)
+    }
+
+    pub fn validate_with_limits(
+        &self,
+        limits: &ProtocolLimits,
+    ) -> Result<(), alloc::vec::Vec<ValidationError>> {
+        let mut errors = alloc::vec::Vec::new();
+        let TransactionData::V1(data) = self;
+        if let TransactionKind::ProgrammableTransaction1(pt) = &data.kind {
+            pt.check(limits, &mut errors);
+        }
+        check_size(self, limits, &mut errors);
+        into_result(errors)
+    }
+}
+
+/// A value with the intent it is signed under, `bcs(intent) || bcs(value)`.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Serialize, Deserialize)]
+pub struct IntentMessage<T> {
+    pub intent: Intent,
+    pub value: T,
+}
+
+impl<T> IntentMessage<T> {
+    pub fn new(intent: Intent, value: T) -> Self {
+        Self { intent, value }
+    }
+}
+
+/// A transaction signature of either a single key or a multisig account.
+///
+/// Both are encoded as their `flag || ...` bytes: length prefixed in BCS
+/// and base64 in human-readable formats (JSON).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenericSignature {
+    Signature(SerializedSignature),
+    MultiSig(alloc::boxed::Box<MultiSig>),
+}
+
+impl GenericSignature {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+        match bytes.first() {
+            Some(&MULTISIG_FLAG) => MultiSig::from_bytes(bytes).map(|multisig| multisig.into()),
+            _ => SerializedSignature::from_bytes(bytes).map(Self::Signature),
+        }
+    }
+
+    pub fn to_bytes(&self) -> MultiSigBytes {
+        match self {
+            GenericSignature::Signature(signature) => {
+                MultiSigBytes::from_slice(signature.as_bytes())
+                    .expect("single key signatures are smaller than multisigs")
+            }
+            GenericSignature::MultiSig(multisig) => multisig.to_bytes(),
+        }
+    }
+
+    /// The address of the account the signature authorizes.
+    pub fn signer_address(&self) -> IotaAddress {
+        match self {
+            GenericSignature::Signature(signature) => signature.signer_address(),
+            GenericSignature::MultiSig(multisig) => multisig.address(),
+        }
+    }
+
+    /// Verifies the signature over `tx_data` and returns the signer address.
+    pub fn verify(&self, tx_data: &TransactionData) -> Result<IotaAddress, CryptoError> {
+        match self {
+            GenericSignature::Signature(signature) => {
+                signature.verify_bcs(tx_data, Intent::iota_transaction())
+            }
+            GenericSignature::MultiSig(multisig) => {
+                multisig.verify_bcs(tx_data, Intent::iota_transaction())
+            }
+        }
+    }
+}
+
+impl From<SerializedSignature> for GenericSignature {
+    fn from(signature: SerializedSignature) -> Self {
+        Self::Signature(signature)
+    }
+}
+
+impl From<MultiSig> for GenericSignature {
+    fn from(multisig: MultiSig) -> Self {
+        Self::MultiSig(alloc::boxed::Box::new(multisig))
+    }
+}
+
+impl Serialize for GenericSignature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let bytes = self.to_bytes();
+        if serializer.is_human_readable() {
+            MultiSigBase64Signature::new(&bytes)
+                .map_err(|_| serde::ser::Error::custom("Failed to encode signature"))?
+                .serialize(serializer)
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for GenericSignature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let invalid = |_| serde::de::Error::custom("Invalid signature");
+        if deserializer.is_human_readable() {
+            let base64_str =
+                heapless::String::<MAX_BASE64_SIGNATURE_LENGTH>::deserialize(deserializer)?;
+            // Base64 decoding needs some slack for padding.
+            let mut buf = [0u8; MAX_MULTISIG_SIZE + 3];
+            let size = BASE64_STANDARD
+                .decode_slice(base64_str.as_str(), &mut buf)
+                .map_err(|_| serde::de::Error::custom("Invalid base64 signature"))?;
+            GenericSignature::from_bytes(&buf[..size]).map_err(invalid)
+        } else {
+            let bytes = MultiSigBytes::deserialize(deserializer)?;
+            GenericSignature::from_bytes(&bytes).map_err(invalid)
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
+pub struct SenderSignedTransaction {
+    pub intent_message: IntentMessage<TransactionData>,
+    /// One signature per required signer: the sender and, if different, the gas owner.
+    pub tx_signatures: alloc::vec::Vec<GenericSignature>,
+}
+
+/// A signed transaction as the node accepts and stores it.
+///
+/// Encoded as a vector holding exactly one `SenderSignedTransaction`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SenderSignedData(SenderSignedTransaction);
+
+impl SenderSignedData {
+    /// Wraps `tx_data` under the default transaction intent.
+    pub fn new(tx_data: TransactionData, tx_signatures: alloc::vec::Vec<GenericSignature>) -> Self {
+        Self(SenderSignedTransaction {
+            intent_message: IntentMessage::new(Intent::iota_transaction(), tx_data),
+            tx_signatures,
+        })
+    }
+
+    pub fn inner(&self) -> &SenderSignedTransaction {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> SenderSignedTransaction {
+        self.0
+    }
+
+    pub fn intent_message(&self) -> &IntentMessage<TransactionData> {
+        &self.0.intent_message
+    }
+
+    pub fn transaction_data(&self) -> &TransactionData {
+        &self.0.intent_message.value
+    }
+
+    pub fn tx_signatures(&self) -> &[GenericSignature] {
+        &self.0.tx_signatures
+    }
+
+    pub fn add_signature(&mut self, signature: GenericSignature) {
+        self.0.tx_signatures.push(signature);
+    }
+
+    /// The digest of the transaction data, the signatures are not part of it.
+    pub fn digest(&self) -> Result<Digest, BcsStreamError> {
+        self.transaction_data().digest()
+    }
+}
+
+impl Serialize for SenderSignedData {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        core::slice::from_ref(&self.0).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SenderSignedData {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut transactions =
+            alloc::vec::Vec::<SenderSignedTransaction>::deserialize(deserializer)?;
+        match transactions.pop() {
+            Some(transaction) if transactions.is_empty() => Ok(Self(transaction)),
+            _ => Err(serde::de::Error::custom(
+                "Expected exactly one sender signed transaction",
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests2 {
     use super::*;
@@ -202,15 +1177,15 @@ mod tests2 {
     async fn test_encode() {
         let tx = TransactionData::V1(TransactionDataV1 {
             expiration: TransactionExpiration::None,
-            sender: ObjectID::new([
+            sender: IotaAddress::new([
                 150, 210, 207, 1, 112, 242, 209, 131, 20, 109, 36, 202, 26, 144, 76, 105, 214, 185,
                 186, 117, 50, 228, 162, 206, 56, 106, 245, 177, 152, 64, 149, 150,
             ]),
             kind: ProgrammableTransaction1(ProgrammableTransaction {
                 commands: alloc::vec![Command::MoveCall(Box::from(ProgrammableMoveCall {
-                    package: ObjectID([0u8; 32]),
-                    module: Identifier(Box::from("temperature_sensors")),
-                    function: Identifier(Box::from("push_temperature_reading")),
+                    package: ObjectID::new([0u8; 32]),
+                    module: Identifier::from_static("temperature_sensors"),
+                    function: Identifier::from_static("push_temperature_reading"),
                     type_arguments: vec![],
                     arguments: vec![
                         Argument::Input(0),
@@ -230,12 +1205,16 @@ mod tests2 {
             }),
             gas_data: GasData {
                 budget: 99999,
-                owner: ObjectID::new([
+                owner: IotaAddress::new([
                     150, 210, 207, 1, 112, 242, 209, 131, 20, 109, 36, 202, 26, 144, 76, 105, 214,
                     185, 186, 117, 50, 228, 162, 206, 56, 106, 245, 177, 152, 64, 149, 150,
                 ]),
                 price: 8888,
-                payment: alloc::vec![(ObjectID([8u8; 32]), SequenceNumber(3), Digest([9u8; 32]))],
+                payment: alloc::vec![ObjectRef::new(
+                    ObjectID::new([8u8; 32]),
+                    SequenceNumber::new(3),
+                    Digest::new([9u8; 32])
+                )],
             },
         });
 
@@ -283,4 +1262,515 @@ mod tests2 {
             }
         }
     }
+
+    #[tokio::test]
+    async fn test_transaction_digest() {
+        let move_call = "AAAFAAF7AAsKTHVrYXMgSG9tZQAEAH0AAAAB/wAIAQAAAAAAAAABAMf/nu/aCgOsDfHUaZrN3TD+g8f0TeYhj/yWm9wg0xEOE3RlbXBlcmF0dXJlX3NlbnNvcnMYcHVzaF90ZW1wZXJhdHVyZV9yZWFkaW5nAAUBAAABAQABAgABAwABBACW0s8BcPLRgxRtJMoakExp1rm6dTLkos44avWxmECVlgEAjpZCZJz+EzMlxbhzVc5C7JFdI2OKnXuSaR12/N393gMAAAAAAAAAIK6FX5Rt9gNSKze0pLtiFWdqUtMNtvbWT4qUoKfIR31pltLPAXDy0YMUbSTKGpBMada5unUy5KLOOGr1sZhAlZboAwAAAAAAAADh9QUAAAAAAA==";
+        let mut tx_bytes = [0u8; 1024];
+        let tx_size = BASE64_STANDARD
+            .decode_slice(move_call, &mut tx_bytes)
+            .unwrap();
+        let tx = from_bytes::<TransactionData>(&tx_bytes[..tx_size]).unwrap();
+
+        let digest = tx.digest().unwrap();
+        assert_eq!(
+            digest.as_base58().unwrap().as_str(),
+            "BZYdAaxvtFXgoJ1RDdPztsDpiXC7WsG3J6BcQ5FP8D3k"
+        );
+        assert_eq!(
+            Digest::from_base58(digest.as_base58().unwrap().as_str()).unwrap(),
+            digest
+        );
+    }
+
+    #[tokio::test]
+    async fn test_object_ref_formats() {
+        let object_ref = ObjectRef::new(
+            ObjectID::from_hex("0x5").unwrap(),
+            SequenceNumber::new(3),
+            Digest::from_base58("BZYdAaxvtFXgoJ1RDdPztsDpiXC7WsG3J6BcQ5FP8D3k").unwrap(),
+        );
+
+        // BCS: same bytes as the `(ObjectID, SequenceNumber, Digest)` tuple.
+        assert_eq!(
+            to_bytes(&object_ref).unwrap(),
+            to_bytes(&(
+                *object_ref.object_id.as_bytes(),
+                3u64,
+                object_ref.digest.as_bytes().as_slice()
+            ))
+            .unwrap()
+        );
+        assert_eq!(
+            from_bytes::<ObjectRef>(&to_bytes(&object_ref).unwrap()).unwrap(),
+            object_ref
+        );
+
+        // JSON: the gas station representation.
+        let json = r#"{"objectId":"0x0000000000000000000000000000000000000000000000000000000000000005","version":3,"digest":"BZYdAaxvtFXgoJ1RDdPztsDpiXC7WsG3J6BcQ5FP8D3k"}"#;
+        let mut buf = [0u8; 256];
+        let size = serde_json_core::to_slice(&object_ref, &mut buf).unwrap();
+        assert_eq!(&buf[..size], json.as_bytes());
+        let (parsed, _): (ObjectRef, _) = serde_json_core::from_str(json).unwrap();
+        assert_eq!(parsed, object_ref);
+
+        assert_eq!(
+            object_ref.object_id.to_string(),
+            "0x0000000000000000000000000000000000000000000000000000000000000005"
+        );
+        assert_eq!(
+            object_ref.digest.to_string(),
+            "BZYdAaxvtFXgoJ1RDdPztsDpiXC7WsG3J6BcQ5FP8D3k"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_type_tag_strings() {
+        let coin = TypeTag::from_str("0x2::coin::Coin<0x2::iota::IOTA>").unwrap();
+        let iota = StructTag {
+            address: IotaAddress::from_str("0x2").unwrap(),
+            module: Identifier::from_static("iota"),
+            name: Identifier::from_static("IOTA"),
+            type_params: alloc::vec![],
+        };
+        assert_eq!(
+            coin,
+            TypeTag::Struct(Box::new(StructTag {
+                address: IotaAddress::from_str("0x2").unwrap(),
+                module: Identifier::from_static("coin"),
+                name: Identifier::from_static("Coin"),
+                type_params: alloc::vec![TypeTag::Struct(Box::new(iota.clone()))],
+            }))
+        );
+        assert_eq!(coin.to_string(), "0x2::coin::Coin<0x2::iota::IOTA>");
+
+        // Long addresses and extra whitespace parse to the same tag.
+        assert_eq!(
+            TypeTag::from_str(
+                " 0x0000000000000000000000000000000000000000000000000000000000000002 :: coin::Coin< 0x02::iota::IOTA > "
+            )
+            .unwrap(),
+            coin
+        );
+        assert_eq!(StructTag::from_str("0x2::iota::IOTA").unwrap(), iota);
+
+        for canonical in [
+            "vector<u8>",
+            "vector<vector<u256>>",
+            "0x2::dynamic_field::Field<address, vector<0x1::string::String>>",
+            "0x0::_m::S<bool, u16, u32, u64, u128, signer>",
+        ] {
+            assert_eq!(TypeTag::from_str(canonical).unwrap().to_string(), canonical);
+        }
+
+        assert_eq!(
+            TypeTag::from_str("vector<u8"),
+            Err(TypeTagParseError::UnexpectedEnd)
+        );
+        assert_eq!(
+            TypeTag::from_str("vector<u8>>"),
+            Err(TypeTagParseError::UnexpectedToken(10))
+        );
+        assert_eq!(
+            TypeTag::from_str("u7"),
+            Err(TypeTagParseError::UnexpectedToken(0))
+        );
+        assert_eq!(
+            TypeTag::from_str("0x2::1coin::Coin"),
+            Err(TypeTagParseError::InvalidIdentifier(5))
+        );
+        assert_eq!(
+            TypeTag::from_str("0xg::coin::Coin"),
+            Err(TypeTagParseError::InvalidAddress(AddressError::InvalidHex))
+        );
+        assert_eq!(
+            StructTag::from_str("vector<u8>"),
+            Err(TypeTagParseError::NotAStruct)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_identifier() {
+        const COIN: Identifier = Identifier::from_static("coin");
+        assert_eq!(COIN, Identifier::new("coin".into()).unwrap());
+        assert_eq!(COIN.to_string(), "coin");
+
+        for valid in ["a", "A", "_a", "__", "push_reading", "Coin2", "_0"] {
+            assert!(Identifier::is_valid(valid), "{}", valid);
+            assert_eq!(Identifier::from_str(valid).unwrap().as_str(), valid);
+        }
+        assert_eq!(Identifier::from_str(""), Err(IdentifierError::Empty));
+        assert_eq!(
+            Identifier::from_str("_"),
+            Err(IdentifierError::InvalidCharacter(0))
+        );
+        assert_eq!(
+            Identifier::from_str("1coin"),
+            Err(IdentifierError::InvalidCharacter(0))
+        );
+        assert_eq!(
+            Identifier::from_str("push-reading"),
+            Err(IdentifierError::InvalidCharacter(4))
+        );
+        assert_eq!(
+            Identifier::from_str("push_readingé"),
+            Err(IdentifierError::InvalidCharacter(12))
+        );
+
+        // BCS round trip, and decoding rejects invalid names.
+        let bytes = to_bytes(&COIN).unwrap();
+        assert_eq!(bytes, b"\x04coin");
+        assert_eq!(from_bytes::<Identifier>(&bytes).unwrap(), COIN);
+        assert!(from_bytes::<Identifier>(b"\x04co-n").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_system_transaction_kinds() {
+        let randomness = TransactionKind::RandomnessStateUpdate(RandomnessStateUpdate {
+            epoch: 7,
+            randomness_round: 9,
+            random_bytes: alloc::vec![0xaa, 0xbb],
+            randomness_obj_initial_shared_version: SequenceNumber::new(1),
+        });
+        let mut expected = alloc::vec![5];
+        expected.extend_from_slice(&7u64.to_le_bytes());
+        expected.extend_from_slice(&9u64.to_le_bytes());
+        expected.extend_from_slice(&[2, 0xaa, 0xbb]);
+        expected.extend_from_slice(&1u64.to_le_bytes());
+        assert_eq!(to_bytes(&randomness).unwrap(), expected);
+        assert!(randomness.is_system_transaction());
+
+        let end_of_epoch = TransactionKind::EndOfEpoch(alloc::vec![
+            EndOfEpochTransactionKind::ChangeEpochV2(ChangeEpochV2 {
+                epoch: 8,
+                protocol_version: 3,
+                storage_charge: 100,
+                computation_charge: 200,
+                computation_charge_burned: 150,
+                storage_rebate: 50,
+                non_refundable_storage_fee: 1,
+                epoch_start_timestamp_ms: 1_700_000_000_000,
+                system_packages: alloc::vec![(
+                    SequenceNumber::new(2),
+                    alloc::vec![alloc::vec![0xa1, 0x1c, 0xeb, 0x0b]],
+                    alloc::vec![ObjectID::from_hex("0x1").unwrap()],
+                )],
+            }),
+            EndOfEpochTransactionKind::AuthenticatorStateExpire(AuthenticatorStateExpire {
+                min_epoch: 6,
+                authenticator_obj_initial_shared_version: SequenceNumber::new(4),
+            }),
+        ]);
+        let bytes = to_bytes(&end_of_epoch).unwrap();
+        assert_eq!(&bytes[..3], &[4, 2, 1]);
+
+        let genesis = TransactionKind::Genesis(GenesisTransaction {
+            objects: alloc::vec![GenesisObject::RawObject {
+                data: ObjectData::Move(MoveObject {
+                    type_: MoveObjectType::GasCoin,
+                    version: SequenceNumber::new(1),
+                    contents: alloc::vec![0; 40],
+                }),
+                owner: Owner::AddressOwner(IotaAddress::from_str("0xa11ce").unwrap()),
+            }],
+            events: alloc::vec![],
+        });
+        let prologue = TransactionKind::ConsensusCommitPrologue(ConsensusCommitPrologueV1 {
+            epoch: 8,
+            round: 10,
+            sub_dag_index: None,
+            commit_timestamp_ms: 1_700_000_000_123,
+            consensus_commit_digest: Digest::new([3; 32]),
+            consensus_determined_version_assignments:
+                ConsensusDeterminedVersionAssignments::CancelledTransactions(alloc::vec![]),
+        });
+        let jwks = TransactionKind::AuthenticatorStateUpdate(AuthenticatorStateUpdateV1 {
+            epoch: 8,
+            round: 11,
+            new_active_jwks: alloc::vec![ActiveJwk {
+                jwk_id: JwkId {
+                    iss: "https://accounts.google.com".into(),
+                    kid: "1".into(),
+                },
+                jwk: Jwk {
+                    kty: "RSA".into(),
+                    e: "AQAB".into(),
+                    n: "0vx7".into(),
+                    alg: "RS256".into(),
+                },
+                epoch: 8,
+            }],
+            authenticator_obj_initial_shared_version: SequenceNumber::new(4),
+        });
+
+        for (tag, kind) in [(1, genesis), (2, prologue), (3, jwks), (4, end_of_epoch)] {
+            let bytes = to_bytes(&kind).unwrap();
+            assert_eq!(bytes[0], tag);
+            assert_eq!(from_bytes::<TransactionKind>(&bytes).unwrap(), kind);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sender_signed_data() {
+        use crate::crypto::{Crypto, KeyPair, MultiSigMember, MultiSigPublicKey, SignatureScheme};
+
+        let sender = Crypto::from_seed([0; 32]);
+        let sponsor = KeyPair::from_seed(SignatureScheme::Secp256k1, [1; 32]).unwrap();
+        let tx = TransactionData::V1(TransactionDataV1 {
+            kind: ProgrammableTransaction1(ProgrammableTransaction {
+                inputs: alloc::vec![CallArg::Pure(alloc::vec![1])],
+                commands: alloc::vec![],
+            }),
+            sender: sender.public_address(),
+            gas_data: GasData {
+                payment: alloc::vec![ObjectRef::new(
+                    ObjectID::from_hex("0x5").unwrap(),
+                    SequenceNumber::new(3),
+                    Digest::new([9; 32]),
+                )],
+                owner: sponsor.public_address(),
+                price: 1000,
+                budget: 1_000_000,
+            },
+            expiration: TransactionExpiration::None,
+        });
+
+        let sender_sig = SerializedSignature::from_bytes(
+            &sender.sign_bcs(Intent::iota_transaction(), &tx).unwrap(),
+        )
+        .unwrap();
+        let sponsor_sig = SerializedSignature::from_bytes(
+            &sponsor.sign_bcs(Intent::iota_transaction(), &tx).unwrap(),
+        )
+        .unwrap();
+        let mut signed = SenderSignedData::new(tx.clone(), alloc::vec![sender_sig.clone().into()]);
+        signed.add_signature(sponsor_sig.clone().into());
+
+        let bytes = to_bytes(&signed).unwrap();
+        let tx_bytes = to_bytes(&tx).unwrap();
+        // One transaction, the default intent, the data, then two length prefixed signatures.
+        assert_eq!(&bytes[..4], &[1, 0, 0, 0]);
+        assert_eq!(&bytes[4..4 + tx_bytes.len()], tx_bytes.as_slice());
+        let mut signatures = alloc::vec![2, 97];
+        signatures.extend_from_slice(sender_sig.as_bytes());
+        signatures.push(98);
+        signatures.extend_from_slice(sponsor_sig.as_bytes());
+        assert_eq!(&bytes[4 + tx_bytes.len()..], signatures.as_slice());
+
+        let decoded = from_bytes::<SenderSignedData>(&bytes).unwrap();
+        assert_eq!(decoded, signed);
+        assert_eq!(decoded.digest().unwrap(), tx.digest().unwrap());
+        for signature in decoded.tx_signatures() {
+            assert_eq!(
+                signature.verify(decoded.transaction_data()).unwrap(),
+                signature.signer_address()
+            );
+        }
+        assert_eq!(
+            decoded.tx_signatures()[1].signer_address(),
+            sponsor.public_address()
+        );
+
+        // A 1-of-2 multisig sender signature.
+        let multisig_pk = MultiSigPublicKey::new(
+            &[
+                MultiSigMember::new(SignatureScheme::Ed25519, &sender.public_key_bytes(), 1)
+                    .unwrap(),
+                MultiSigMember::new(SignatureScheme::Secp256k1, &sponsor.public_key_bytes(), 1)
+                    .unwrap(),
+            ],
+            1,
+        )
+        .unwrap();
+        let multisig = MultiSig::combine(multisig_pk, &[sender_sig]).unwrap();
+        let signed = SenderSignedData::new(tx.clone(), alloc::vec![multisig.clone().into()]);
+        let bytes = to_bytes(&signed).unwrap();
+        let decoded = from_bytes::<SenderSignedData>(&bytes).unwrap();
+        assert_eq!(decoded, signed);
+        assert_eq!(
+            decoded.tx_signatures()[0].verify(&tx).unwrap(),
+            multisig.address()
+        );
+
+        // Not exactly one transaction.
+        assert!(from_bytes::<SenderSignedData>(&[0]).is_err());
+        let mut two = alloc::vec![2];
+        two.extend_from_slice(&bytes[1..]);
+        two.extend_from_slice(&bytes[1..]);
+        assert!(from_bytes::<SenderSignedData>(&two).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_generic_signature_json() {
+        let base64 = "AJ1ikTF3rimOITnSJuiSHd2yTe67yllUwJDPHXZq8X2mMF6Hl4iesr3tpoqhmaM2UWOHv8s5FqX6gOSkzGZ+KwU7aie8zrakLWKjqNAqbw1zZTIVdx3iQ6Y6wEihi1naKQ==";
+        let signature = GenericSignature::from_bytes(
+            SerializedSignature::from_base64(base64).unwrap().as_bytes(),
+        )
+        .unwrap();
+
+        let mut buf = [0u8; 256];
+        let size = serde_json_core::to_slice(&signature, &mut buf).unwrap();
+        assert_eq!(&buf[..size], alloc::format!("\"{}\"", base64).as_bytes());
+        let (parsed, _): (GenericSignature, _) = serde_json_core::from_slice(&buf[..size]).unwrap();
+        assert_eq!(parsed, signature);
+    }
+
+    #[tokio::test]
+    async fn test_validate_programmable_transaction() {
+        let coin = CallArg::Object(ObjectArg::ImmOrOwnedObject(ObjectRef::new(
+            ObjectID::from_hex("0x5").unwrap(),
+            SequenceNumber::new(3),
+            Digest::ZERO,
+        )));
+        let valid = ProgrammableTransaction {
+            inputs: alloc::vec![CallArg::Pure(to_bytes(&100u64).unwrap()), coin.clone()],
+            commands: alloc::vec![
+                Command::SplitCoins(Argument::GasCoin, alloc::vec![Argument::Input(0)]),
+                Command::MergeCoins(
+                    Argument::Input(1),
+                    alloc::vec![Argument::NestedResult(0, 0)]
+                ),
+                Command::MoveCall(Box::new(ProgrammableMoveCall {
+                    package: ObjectID::from_hex("0x42").unwrap(),
+                    module: Identifier::from_static("temperature"),
+                    function: Identifier::from_static("push_reading"),
+                    type_arguments: alloc::vec![],
+                    arguments: alloc::vec![Argument::GasCoin, Argument::Input(1)],
+                })),
+            ],
+        };
+        assert_eq!(valid.validate(), Ok(()));
+
+        let invalid = ProgrammableTransaction {
+            inputs: alloc::vec![CallArg::Pure(alloc::vec![0; 20]), coin],
+            commands: alloc::vec![
+                Command::SplitCoins(
+                    Argument::Input(1),
+                    alloc::vec![Argument::Input(0), Argument::Result(0)]
+                ),
+                Command::MergeCoins(Argument::Input(2), alloc::vec![Argument::GasCoin]),
+                Command::TransferObjects(
+                    alloc::vec![Argument::NestedResult(0, 2), Argument::NestedResult(3, 0)],
+                    Argument::Input(0)
+                ),
+                Command::MakeMoveVec(None, alloc::vec![]),
+            ],
+        };
+        let limits = ProtocolLimits {
+            max_pure_argument_size: 16,
+            ..ProtocolLimits::MAINNET
+        };
+        assert_eq!(
+            invalid.validate_with_limits(&limits),
+            Err(alloc::vec![
+                ValidationError::PureArgumentTooLarge {
+                    input: 0,
+                    size: 20,
+                    max: 16
+                },
+                ValidationError::InvalidResult {
+                    command: 0,
+                    result: 0
+                },
+                ValidationError::InvalidInput {
+                    command: 1,
+                    input: 2
+                },
+                ValidationError::InvalidGasCoinUsage { command: 1 },
+                ValidationError::InvalidNestedResult {
+                    command: 2,
+                    result: 0,
+                    index: 2
+                },
+                ValidationError::InvalidResult {
+                    command: 2,
+                    result: 3
+                },
+                ValidationError::EmptyArguments { command: 3 },
+            ])
+        );
+
+        let limits = ProtocolLimits {
+            max_input_objects: 0,
+            max_programmable_tx_commands: 3,
+            max_tx_size_bytes: 64,
+            ..ProtocolLimits::MAINNET
+        };
+        let errors = valid.validate_with_limits(&limits).unwrap_err();
+        assert_eq!(
+            errors,
+            alloc::vec![
+                ValidationError::TooManyInputObjects { count: 1, max: 0 },
+                ValidationError::TooManyInputs { count: 2, max: 0 },
+                ValidationError::TooManyCommands { count: 3, max: 3 },
+                ValidationError::TransactionTooLarge {
+                    size: serialized_size(&valid).unwrap(),
+                    max: 64
+                },
+            ]
+        );
+
+        // Pure inputs count towards twice the object limit, commands must stay below the limit.
+        let limits = ProtocolLimits {
+            max_input_objects: 1,
+            max_programmable_tx_commands: 4,
+            ..ProtocolLimits::MAINNET
+        };
+        assert_eq!(valid.validate_with_limits(&limits), Ok(()));
+        let split = Command::SplitCoins(Argument::GasCoin, alloc::vec![Argument::Input(0)]);
+        let max_commands = ProgrammableTransaction {
+            inputs: alloc::vec![CallArg::Pure(to_bytes(&1u64).unwrap())],
+            commands: alloc::vec![split; ProtocolLimits::MAINNET.max_programmable_tx_commands],
+        };
+        assert_eq!(
+            max_commands.validate(),
+            Err(alloc::vec![ValidationError::TooManyCommands {
+                count: 1024,
+                max: 1024
+            }])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_transaction_data_size() {
+        let pt = ProgrammableTransaction {
+            inputs: alloc::vec![CallArg::Pure(to_bytes(&100u64).unwrap())],
+            commands: alloc::vec![Command::SplitCoins(
+                Argument::GasCoin,
+                alloc::vec![Argument::Input(0)]
+            )],
+        };
+        let pt_size = serialized_size(&pt).unwrap();
+        let tx = TransactionData::V1(TransactionDataV1 {
+            kind: TransactionKind::ProgrammableTransaction1(pt.clone()),
+            sender: IotaAddress::new([1; 32]),
+            gas_data: GasData {
+                payment: alloc::vec![ObjectRef::new(
+                    ObjectID::from_hex("0x5").unwrap(),
+                    SequenceNumber::new(3),
+                    Digest::ZERO,
+                )],
+                owner: IotaAddress::new([1; 32]),
+                price: 1000,
+                budget: 10_000_000,
+            },
+            expiration: TransactionExpiration::None,
+        });
+        let tx_size = serialized_size(&tx).unwrap();
+        assert!(tx_size > pt_size);
+
+        let limits = ProtocolLimits {
+            max_tx_size_bytes: pt_size,
+            ..ProtocolLimits::MAINNET
+        };
+        assert_eq!(pt.validate_with_limits(&limits), Ok(()));
+        assert_eq!(
+            tx.validate_with_limits(&limits),
+            Err(alloc::vec![ValidationError::TransactionTooLarge {
+                size: tx_size,
+                max: pt_size
+            }])
+        );
+        assert_eq!(tx.validate(), Ok(()));
+    }
 }